@@ -0,0 +1,71 @@
+use crate::{Exun, RawUnexpected, Unexpected, UnexpectedError};
+
+/// Routes a value into either the [`Expected`] or [`Unexpected`] side of an
+/// [`Exun`].
+///
+/// Implement this for your own error types to decide, once, which of their
+/// cases you expect to handle and which you don't. Callers can then simply
+/// write `error.classify()` instead of repeating the same `match` everywhere.
+/// For an enum where each variant is either expected or unexpected, the
+/// [`impl_classify!`] macro can write the implementation instead.
+///
+/// # Examples
+///
+/// ```
+/// use exun::*;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum DecodeError {
+///     UnsupportedFormat(String),
+///     Truncated,
+///     Corrupt(&'static str),
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Unsupported(String);
+///
+/// impl Classify<Unsupported, DecodeError> for DecodeError {
+///     fn classify(self) -> Exun<Unsupported, DecodeError> {
+///         match self {
+///             // users can give us any file, so this is expected
+///             DecodeError::UnsupportedFormat(format) => Expected(Unsupported(format)),
+///             // but we never expect a file to be damaged
+///             error => Unexpected(error),
+///         }
+///     }
+/// }
+///
+/// let error = DecodeError::UnsupportedFormat("webp".to_string());
+/// assert_eq!(error.classify(), Expected(Unsupported("webp".to_string())));
+///
+/// let error = DecodeError::Truncated;
+/// assert_eq!(error.classify(), Unexpected(DecodeError::Truncated));
+///
+/// let error = DecodeError::Corrupt("bad header");
+/// assert_eq!(error.classify(), Unexpected(DecodeError::Corrupt("bad header")));
+/// ```
+///
+/// [`Expected`]: crate::Expected
+/// [`impl_classify!`]: crate::impl_classify
+pub trait Classify<E, U> {
+	/// Converts `self` into an [`Exun<E, U>`].
+	fn classify(self) -> Exun<E, U>;
+}
+
+impl<E, U> Classify<E, U> for Exun<E, U> {
+	fn classify(self) -> Self {
+		self
+	}
+}
+
+impl<E> Classify<E, Self> for RawUnexpected {
+	fn classify(self) -> Exun<E, Self> {
+		Unexpected(self)
+	}
+}
+
+impl<E> Classify<E, Self> for UnexpectedError {
+	fn classify(self) -> Exun<E, Self> {
+		Unexpected(self)
+	}
+}
@@ -1,8 +1,25 @@
+use core::convert::Infallible;
 use core::fmt::{self, Debug, Display};
+use core::iter::{FromIterator, FusedIterator, Product, Sum};
+use core::ops::Deref;
 
+#[cfg(all(feature = "quickcheck", not(feature = "std")))]
+use alloc::boxed::Box;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::collections::BTreeMap;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::string::{String, ToString};
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+
+#[cfg(all(feature = "core-error", not(feature = "std")))]
+use core::error::Error;
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
 #[cfg(feature = "std")]
 use std::error::Error;
 
+use crate::unexpected::DisplayChain;
 use crate::{RawUnexpected, UnexpectedError};
 
 pub use Exun::{Expected, Unexpected};
@@ -11,7 +28,13 @@ pub use Exun::{Expected, Unexpected};
 /// ([`Expected`]) or an unexpected type ([`Unexpected`]).
 ///
 /// See the [crate documentation](crate) for details.
+///
+/// With the `serde` feature, this can be serialized and deserialized as an
+/// externally tagged enum, i.e. `{"Expected": ...}` or `{"Unexpected": ...}`.
+/// [`RawUnexpected`] and [`UnexpectedError`] don't support this, because they
+/// hold boxed trait objects.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Exun<E, U> {
 	/// Contains the expected type
 	Expected(E),
@@ -28,7 +51,73 @@ impl<E: Display, U: Display> Display for Exun<E, U> {
 	}
 }
 
-#[cfg(feature = "std")]
+/// The default value is always [`Expected`], containing the default value of
+/// `E`.
+///
+/// # Examples
+///
+/// ```
+/// use exun::*;
+///
+/// assert_eq!(Exun::<i32, String>::default(), Expected(0));
+///
+/// #[derive(Default)]
+/// struct Report {
+///     status: Exun<String, u32>,
+/// }
+///
+/// assert_eq!(Report::default().status, Expected(String::new()));
+/// ```
+impl<E: Default, U> Default for Exun<E, U> {
+	fn default() -> Self {
+		Expected(E::default())
+	}
+}
+
+/// Generates each variant with equal probability. Shrinking is done by the
+/// contained value.
+#[cfg(feature = "quickcheck")]
+impl<E: quickcheck::Arbitrary, U: quickcheck::Arbitrary> quickcheck::Arbitrary for Exun<E, U> {
+	fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+		if <bool as quickcheck::Arbitrary>::arbitrary(g) {
+			Expected(E::arbitrary(g))
+		} else {
+			Unexpected(U::arbitrary(g))
+		}
+	}
+
+	fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+		match self {
+			Expected(e) => Box::new(e.shrink().map(Expected)),
+			Unexpected(u) => Box::new(u.shrink().map(Unexpected)),
+		}
+	}
+}
+
+/// Generates each variant with equal probability.
+#[cfg(feature = "proptest")]
+impl<E: proptest::arbitrary::Arbitrary, U: proptest::arbitrary::Arbitrary>
+	proptest::arbitrary::Arbitrary for Exun<E, U>
+{
+	type Parameters = (E::Parameters, U::Parameters);
+	#[allow(clippy::type_complexity)]
+	type Strategy = proptest::strategy::TupleUnion<(
+		proptest::strategy::WA<proptest::strategy::Map<E::Strategy, fn(E) -> Self>>,
+		proptest::strategy::WA<proptest::strategy::Map<U::Strategy, fn(U) -> Self>>,
+	)>;
+
+	fn arbitrary_with((e, u): Self::Parameters) -> Self::Strategy {
+		use proptest::arbitrary::any_with;
+		use proptest::strategy::Strategy;
+
+		proptest::prop_oneof![
+			any_with::<E>(e).prop_map(Expected as fn(E) -> Self),
+			any_with::<U>(u).prop_map(Unexpected as fn(U) -> Self),
+		]
+	}
+}
+
+#[cfg(any(feature = "std", feature = "core-error"))]
 impl<E: Error + 'static, U: Error + 'static> Error for Exun<E, U> {
 	fn source(&self) -> Option<&(dyn Error + 'static)> {
 		match self {
@@ -38,7 +127,7 @@ impl<E: Error + 'static, U: Error + 'static> Error for Exun<E, U> {
 	}
 }
 
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", feature = "core-error"))]
 impl<E: Error + 'static> Error for Exun<E, RawUnexpected> {
 	fn source(&self) -> Option<&(dyn Error + 'static)> {
 		match self {
@@ -48,7 +137,7 @@ impl<E: Error + 'static> Error for Exun<E, RawUnexpected> {
 	}
 }
 
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", feature = "core-error"))]
 impl<E: Error, U> From<E> for Exun<E, U> {
 	fn from(e: E) -> Self {
 		Expected(e)
@@ -68,6 +157,40 @@ impl<E> From<RawUnexpected> for Exun<E, UnexpectedError> {
 }
 
 impl<E, U> Exun<E, U> {
+	/// Returns `true` if the value is [`Expected`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x: Exun<i32, &str> = Expected(2);
+	/// assert!(x.is_expected());
+	///
+	/// let x: Exun<i32, &str> = Unexpected("error");
+	/// assert!(!x.is_expected());
+	/// ```
+	pub const fn is_expected(&self) -> bool {
+		matches!(self, Expected(_))
+	}
+
+	/// Returns `true` if the value is [`Unexpected`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x: Exun<i32, &str> = Expected(2);
+	/// assert!(!x.is_unexpected());
+	///
+	/// let x: Exun<i32, &str> = Unexpected("error");
+	/// assert!(x.is_unexpected());
+	/// ```
+	pub const fn is_unexpected(&self) -> bool {
+		matches!(self, Unexpected(_))
+	}
+
 	/// Converts from `Exun<E, U>` to [`Option<E>`].
 	///
 	/// Converts `self` into an [`Option<E>`], consuming `self`, and discarding
@@ -116,147 +239,124 @@ impl<E, U> Exun<E, U> {
 		}
 	}
 
-	/// Converts from `&mut Exun<E, U>` to `Exun<&mut E, &mut U>`.
+	/// Converts from `&Exun<E, U>` to [`Option<&E>`].
+	///
+	/// Unlike [`expected`], this doesn't consume `self`.
 	///
 	/// # Examples
 	///
 	/// ```
 	/// use exun::*;
 	///
-	/// fn mutate(r: &mut Exun<i32, i32>) {
-	///     match r.as_mut() {
-	///         Expected(e) => *e = 42,
-	///         Unexpected(u) => *u = 0,
-	///     }
-	/// }
-	///
-	/// let mut x = Expected(2);
-	/// mutate(&mut x);
-	/// assert_eq!(x.unwrap(), 42);
+	/// let x: Exun<u32, &str> = Expected(404);
+	/// assert_eq!(x.expected_ref(), Some(&404));
 	///
-	/// let mut x = Unexpected(13);
-	/// mutate(&mut x);
-	/// assert_eq!(x.unwrap_unexpected(), 0);
+	/// let x: Exun<u32, &str> = Unexpected("error");
+	/// assert_eq!(x.expected_ref(), None);
 	/// ```
-	pub fn as_mut(&mut self) -> Exun<&mut E, &mut U> {
+	///
+	/// [`expected`]: Self::expected
+	pub const fn expected_ref(&self) -> Option<&E> {
 		match self {
-			Expected(ref mut e) => Expected(e),
-			Unexpected(ref mut u) => Unexpected(u),
+			Expected(e) => Some(e),
+			Unexpected(_) => None,
 		}
 	}
 
-	/// Maps a `Exun<E, U>` to `Exun<T, U>` by applying a function to a
-	/// contained [`Expected`] value, leaving an [`Unexpected`] value
-	/// untouched.
+	/// Converts from `&Exun<E, U>` to [`Option<&U>`].
 	///
-	/// This function can be used to compose the results of two functions.
+	/// Unlike [`unexpected`], this doesn't consume `self`.
 	///
 	/// # Examples
 	///
 	/// ```
 	/// use exun::*;
 	///
-	/// let x: Exun<i32, &str> = Expected(2);
-	/// assert_eq!(x.map(|i| i * 10), Expected(20));
+	/// let x: Exun<u32, &str> = Expected(404);
+	/// assert_eq!(x.unexpected_ref(), None);
 	///
-	/// let x: Exun<i32, &str> = Unexpected("unexpected");
-	/// assert_eq!(x.map(|i| i * 10), Unexpected("unexpected"));
+	/// let x: Exun<u32, &str> = Unexpected("error");
+	/// assert_eq!(x.unexpected_ref(), Some(&"error"));
 	/// ```
-	pub fn map<T, F: FnOnce(E) -> T>(self, op: F) -> Exun<T, U> {
+	///
+	/// [`unexpected`]: Self::unexpected
+	pub const fn unexpected_ref(&self) -> Option<&U> {
 		match self {
-			Expected(e) => Expected(op(e)),
-			Unexpected(u) => Unexpected(u),
+			Expected(_) => None,
+			Unexpected(u) => Some(u),
 		}
 	}
 
-	/// Maps a `Exun<E, U>` to `Exun<E, T>` by applying a function to a
-	/// contained [`Unexpected`] value, leaving an [`Expected`] value
-	/// untouched.
-	///
-	/// This function can be used to pass through an expected result while
-	/// handling an error.
+	/// Returns `true` if the value is [`Expected`] and equal to `other`.
 	///
 	/// # Examples
 	///
 	/// ```
 	/// use exun::*;
 	///
-	/// fn stringify(x: u32) -> String { format!("error code: {x}") }
-	///
-	/// let x: Exun<u32, u32> = Expected(2);
-	/// assert_eq!(x.map_unexpected(stringify), Expected(2));
+	/// let x: Exun<u32, u32> = Expected(404);
+	/// assert!(x.eq_expected(&404));
+	/// assert!(!x.eq_expected(&500));
 	///
-	/// let x: Exun<u32, u32> = Unexpected(13);
-	/// assert_eq!(x.map_unexpected(stringify), Unexpected("error code: 13".to_string()));
+	/// let x: Exun<u32, u32> = Unexpected(404);
+	/// assert!(!x.eq_expected(&404));
 	/// ```
-	pub fn map_unexpected<T, F: FnOnce(U) -> T>(self, op: F) -> Exun<E, T> {
-		match self {
-			Expected(e) => Expected(e),
-			Unexpected(u) => Unexpected(op(u)),
-		}
+	pub fn eq_expected(&self, other: &E) -> bool
+	where
+		E: PartialEq,
+	{
+		matches!(self, Expected(e) if e == other)
 	}
 
-	/// Returns the [`Expected`] value, consuming the `self` value.
-	///
-	/// Because this function may panic, its use is generally discouraged.
-	/// Instead, prefer to use pattern matching and handle the [`Unexpected`]
-	/// case explicitly.
-	///
-	/// # Panics
-	///
-	/// Panics if the value is an [`Unexpected`] value, with a panic message
-	/// including the passed message, and the content of the [`Unexpected`]
-	/// value.
+	/// Returns `true` if the value is [`Unexpected`] and equal to `other`.
 	///
 	/// # Examples
 	///
-	/// ```should_panic
+	/// ```
 	/// use exun::*;
 	///
-	/// let x: Exun<u32, &str> = Exun::Unexpected("error");
-	/// x.expect("Testing expect"); // panics with "testing expect: error"
+	/// let x: Exun<u32, u32> = Unexpected(404);
+	/// assert!(x.eq_unexpected(&404));
+	/// assert!(!x.eq_unexpected(&500));
+	///
+	/// let x: Exun<u32, u32> = Expected(404);
+	/// assert!(!x.eq_unexpected(&404));
 	/// ```
+	pub fn eq_unexpected(&self, other: &U) -> bool
+	where
+		U: PartialEq,
+	{
+		matches!(self, Unexpected(u) if u == other)
+	}
+
+	/// Returns the name of the variant, either `"Expected"` or
+	/// `"Unexpected"`.
 	///
-	/// # Recommended Message Style
+	/// This is useful as a label for logs and metrics, without needing to
+	/// inspect the contained value.
 	///
-	/// We recommend that `expect` messages are used to describe the reason you
-	/// *expect* the `Exun` should be `Expected`.
+	/// # Examples
 	///
-	/// ```should_panic
-	/// let path = std::env::var("IMPORTANT_PATH")
-	///     .expect("env variable `IMPORTANT_PATH` should be set by test.sh");
 	/// ```
+	/// use exun::*;
 	///
-	/// **Hint:** If you're having trouble remembering how to phrase expect
-	/// error messages, remember to focus on the word "should" as in "env
-	/// variable set by blah" or "the given binary should be available and
-	/// executable by the current user".
+	/// let x: Exun<i32, &str> = Expected(2);
+	/// assert_eq!(x.variant_name(), "Expected");
 	///
-	/// For more detail on expect message styles and the reasoning behind the
-	/// recommendation please refer to the section on
-	/// ["Common Message Styles"](https://doc.rust-lang.org/stable/std/error/index.html#common-message-styles)
-	/// in the [`std::error`](https://doc.rust-lang.org/stable/std/error/index.html)
-	/// module docs.
-	pub fn expect(self, msg: &str) -> E
-	where
-		U: Debug,
-	{
+	/// let x: Exun<i32, &str> = Unexpected("error");
+	/// assert_eq!(x.variant_name(), "Unexpected");
+	/// ```
+	pub const fn variant_name(&self) -> &'static str {
 		match self {
-			Self::Expected(e) => e,
-			Self::Unexpected(e) => panic!("{}: {:?}", msg, e),
+			Expected(_) => "Expected",
+			Unexpected(_) => "Unexpected",
 		}
 	}
 
-	/// Returns the contained [`Expected`] value, consuming the `self` value.
-	///
-	/// Because this function may panic, its use is generally discouraged.
-	/// Instead, prefer to use pattern matching and handle the [`Unexpected`]
-	/// case explicitly, or call [`unwrap_or`] or [`unwrap_or_else`].
-	///
-	/// # Panics
+	/// Converts from `&Exun<E, U>` to `Exun<&E, &U>`.
 	///
-	/// Panics if the value is [`Unexpected`], with an panic message provided
-	/// by the [`Unexpected`]'s value.
+	/// Produces a new `Exun`, containing a reference into the original,
+	/// leaving the original in place.
 	///
 	/// # Examples
 	///
@@ -264,106 +364,2323 @@ impl<E, U> Exun<E, U> {
 	/// use exun::*;
 	///
 	/// let x: Exun<u32, &str> = Expected(2);
-	/// assert_eq!(x.unwrap(), 2);
+	/// assert_eq!(x.as_ref().map(|e| e.to_string()), Expected("2".to_string()));
+	///
+	/// let x: Exun<u32, &str> = Unexpected("error");
+	/// assert_eq!(x.as_ref(), Unexpected(&"error"));
 	/// ```
+	pub const fn as_ref(&self) -> Exun<&E, &U> {
+		match self {
+			Expected(e) => Expected(e),
+			Unexpected(u) => Unexpected(u),
+		}
+	}
+
+	/// Converts from `&mut Exun<E, U>` to `Exun<&mut E, &mut U>`.
 	///
-	/// ```should_panic
-	/// use exun::*;
+	/// # Examples
 	///
-	/// let x: Exun<u32, &str> = Unexpected("emergency failure");
-	/// x.unwrap(); // panics with `emergency failure`
 	/// ```
+	/// use exun::*;
 	///
-	/// [`unwrap_or`]: Self::unwrap_or
-	/// [`unwrap_or_else`]: Self::unwrap_or_else
-	pub fn unwrap(self) -> E
-	where
-		U: Debug,
-	{
+	/// fn mutate(r: &mut Exun<i32, i32>) {
+	///     match r.as_mut() {
+	///         Expected(e) => *e = 42,
+	///         Unexpected(u) => *u = 0,
+	///     }
+	/// }
+	///
+	/// let mut x = Expected(2);
+	/// mutate(&mut x);
+	/// assert_eq!(x.unwrap(), 42);
+	///
+	/// let mut x = Unexpected(13);
+	/// mutate(&mut x);
+	/// assert_eq!(x.unwrap_unexpected(), 0);
+	/// ```
+	pub fn as_mut(&mut self) -> Exun<&mut E, &mut U> {
 		match self {
-			Expected(e) => e,
-			Unexpected(u) => panic!("called `Expect::unwrap` on an `Unexpected` value: {:?}", u),
+			Expected(ref mut e) => Expected(e),
+			Unexpected(ref mut u) => Unexpected(u),
 		}
 	}
 
-	/// Returns the contained [`Unexpected`] value, consuming the `self` value.
-	///
-	/// # Panics
+	/// Converts from `&Exun<E, U>` to `Exun<&E::Target, &U>`.
 	///
-	/// Panics if the value is [`Expected`], with an panic message provided by
-	/// the [`Expected`]'s value.
+	/// Coerces the [`Expected`] value via [`Deref`] and returns a new `Exun`
+	/// which borrows from the original.
 	///
 	/// # Examples
 	///
-	/// ```should_panic
+	/// ```
 	/// use exun::*;
 	///
-	/// let x: Exun<u32, &str> = Expected(2);
-	/// x.unwrap_unexpected(); // panics with `2`
+	/// let x: Exun<String, i32> = Expected("text".to_string());
+	/// assert_eq!(x.as_deref(), Expected("text"));
+	///
+	/// let x: Exun<String, i32> = Unexpected(13);
+	/// assert_eq!(x.as_deref(), Unexpected(&13));
 	/// ```
+	pub fn as_deref(&self) -> Exun<&E::Target, &U>
+	where
+		E: Deref,
+	{
+		self.as_ref().map(Deref::deref)
+	}
+
+	/// Converts from `&Exun<E, U>` to `Exun<&E, &U::Target>`.
+	///
+	/// Coerces the [`Unexpected`] value via [`Deref`] and returns a new `Exun`
+	/// which borrows from the original.
+	///
+	/// # Examples
 	///
 	/// ```
 	/// use exun::*;
 	///
-	/// let x: Exun<u32, &str> = Unexpected("emergency failure");
-	/// assert_eq!(x.unwrap_unexpected(), "emergency failure");
+	/// let x: Exun<i32, String> = Unexpected("text".to_string());
+	/// assert_eq!(x.as_deref_unexpected(), Unexpected("text"));
+	///
+	/// let x: Exun<i32, String> = Expected(13);
+	/// assert_eq!(x.as_deref_unexpected(), Expected(&13));
 	/// ```
-	pub fn unwrap_unexpected(self) -> U
+	pub fn as_deref_unexpected(&self) -> Exun<&E, &U::Target>
 	where
-		E: Debug,
+		U: Deref,
 	{
-		match self {
-			Expected(e) => panic!(
-				"called `Expect::unwrap_unexpected` on an `Expected` value: {:?}",
-				e
-			),
-			Unexpected(u) => u,
-		}
+		self.as_ref().map_unexpected(Deref::deref)
 	}
 
-	/// Returns the contained [`Expected`] value or a provided default.
+	/// Returns a slice containing the [`Expected`] value, if there is one.
 	///
-	/// Arguments passed to `unwrap_or` are eagerly evaluated; if you are
-	/// passing the result of a function call, it is recommended to use
-	/// [`unwrap_or_else`], which is lazily evaluated.
+	/// The slice contains one element if the value is [`Expected`], or no
+	/// elements if it is [`Unexpected`].
 	///
 	/// # Examples
 	///
 	/// ```
 	/// use exun::*;
 	///
-	/// let default = 2;
-	/// let x: Exun<u32, &str> = Expected(9);
-	/// assert_eq!(x.unwrap_or(default), 9);
+	/// let x: Exun<i32, &str> = Expected(2);
+	/// assert_eq!(x.expected_as_slice(), &[2]);
 	///
-	/// let x: Exun<u32, &str> = Unexpected("error");
-	/// assert_eq!(x.unwrap_or(default), default);
-	/// ```
+	/// let x: Exun<i32, &str> = Unexpected("error");
+	/// assert!(x.expected_as_slice().is_empty());
 	///
-	/// [`unwrap_or_else`]: Self::unwrap_or_else
-	pub fn unwrap_or(self, default: E) -> E {
+	/// let errors: Vec<Exun<i32, &str>> = vec![Expected(1), Unexpected("error"), Expected(3)];
+	/// let expected: Vec<i32> = errors.iter().flat_map(Exun::expected_as_slice).copied().collect();
+	/// assert_eq!(expected, vec![1, 3]);
+	/// ```
+	pub fn expected_as_slice(&self) -> &[E] {
 		match self {
-			Expected(e) => e,
-			Unexpected(_) => default,
+			Expected(e) => core::slice::from_ref(e),
+			Unexpected(_) => &[],
 		}
 	}
 
-	/// Returns the [`Expected`] value or returns it from a closure.
+	/// Takes the [`Unexpected`] value out of the `Exun`, leaving an
+	/// [`Expected`] value containing `placeholder` in its place.
+	///
+	/// If the value is [`Expected`], it is left untouched, and [`None`] is
+	/// returned.
 	///
 	/// # Examples
 	///
 	/// ```
 	/// use exun::*;
 	///
-	/// fn count(x: &str) -> usize { x.len() }
+	/// let mut x: Exun<i32, &str> = Unexpected("surprise");
+	/// assert_eq!(x.take_unexpected(0), Some("surprise"));
+	/// assert_eq!(x, Expected(0));
 	///
-	/// assert_eq!(Expected(2).unwrap_or_else(count), 2);
-	/// assert_eq!(Unexpected("foo").unwrap_or_else(count), 3);
+	/// let mut x: Exun<i32, &str> = Expected(2);
+	/// assert_eq!(x.take_unexpected(0), None);
+	/// assert_eq!(x, Expected(2));
 	/// ```
-	pub fn unwrap_or_else(self, op: impl FnOnce(U) -> E) -> E {
+	pub fn take_unexpected(&mut self, placeholder: E) -> Option<U> {
 		match self {
-			Expected(e) => e,
-			Unexpected(u) => op(u),
+			Expected(_) => None,
+			Unexpected(_) => core::mem::replace(self, Expected(placeholder)).unexpected(),
 		}
 	}
+
+	/// Maps a `Exun<E, U>` to `Exun<T, U>` by applying a function to a
+	/// contained [`Expected`] value, leaving an [`Unexpected`] value
+	/// untouched.
+	///
+	/// This function can be used to compose the results of two functions.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x: Exun<i32, &str> = Expected(2);
+	/// assert_eq!(x.map(|i| i * 10), Expected(20));
+	///
+	/// let x: Exun<i32, &str> = Unexpected("unexpected");
+	/// assert_eq!(x.map(|i| i * 10), Unexpected("unexpected"));
+	/// ```
+	pub fn map<T, F: FnOnce(E) -> T>(self, op: F) -> Exun<T, U> {
+		match self {
+			Expected(e) => Expected(op(e)),
+			Unexpected(u) => Unexpected(u),
+		}
+	}
+
+	/// Maps a `Exun<E, U>` to `Exun<E, T>` by applying a function to a
+	/// contained [`Unexpected`] value, leaving an [`Expected`] value
+	/// untouched.
+	///
+	/// This function can be used to pass through an expected result while
+	/// handling an error.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// fn stringify(x: u32) -> String { format!("error code: {x}") }
+	///
+	/// let x: Exun<u32, u32> = Expected(2);
+	/// assert_eq!(x.map_unexpected(stringify), Expected(2));
+	///
+	/// let x: Exun<u32, u32> = Unexpected(13);
+	/// assert_eq!(x.map_unexpected(stringify), Unexpected("error code: 13".to_string()));
+	/// ```
+	pub fn map_unexpected<T, F: FnOnce(U) -> T>(self, op: F) -> Exun<E, T> {
+		match self {
+			Expected(e) => Expected(e),
+			Unexpected(u) => Unexpected(op(u)),
+		}
+	}
+
+	/// Maps an `Exun<E, U>` to `Exun<F, V>` by applying `exp` to a contained
+	/// [`Expected`] value, or `unexp` to a contained [`Unexpected`] value.
+	///
+	/// Only one of the functions is called.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x: Exun<&str, u32> = Expected("not found");
+	/// assert_eq!(x.map_both(|e| e.len(), |u| u * 2), Expected(9));
+	///
+	/// let x: Exun<&str, u32> = Unexpected(13);
+	/// assert_eq!(x.map_both(|e| e.len(), |u| u * 2), Unexpected(26));
+	/// ```
+	///
+	/// The other function is never called:
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x: Exun<&str, u32> = Expected("not found");
+	/// let x = x.map_both(|e| e.len(), |_| -> u32 { panic!("called unexp") });
+	/// assert_eq!(x, Expected(9));
+	///
+	/// let x: Exun<&str, u32> = Unexpected(13);
+	/// let x = x.map_both(|_| -> usize { panic!("called exp") }, |u| u * 2);
+	/// assert_eq!(x, Unexpected(26));
+	/// ```
+	pub fn map_both<F, V>(
+		self,
+		exp: impl FnOnce(E) -> F,
+		unexp: impl FnOnce(U) -> V,
+	) -> Exun<F, V> {
+		match self {
+			Expected(e) => Expected(exp(e)),
+			Unexpected(u) => Unexpected(unexp(u)),
+		}
+	}
+
+	/// Returns the provided default (if [`Unexpected`]), or applies a
+	/// function to the contained value (if [`Expected`]).
+	///
+	/// Arguments passed to `map_or` are eagerly evaluated; if you are passing
+	/// the result of a function call, it is recommended to use
+	/// [`map_or_else`], which is lazily evaluated.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x: Exun<&str, u32> = Expected("foo");
+	/// assert_eq!(x.map_or(42, |e| e.len()), 3);
+	///
+	/// let x: Exun<&str, u32> = Unexpected(13);
+	/// assert_eq!(x.map_or(42, |e| e.len()), 42);
+	/// ```
+	///
+	/// [`map_or_else`]: Self::map_or_else
+	pub fn map_or<T, F: FnOnce(E) -> T>(self, default: T, f: F) -> T {
+		match self {
+			Expected(e) => f(e),
+			Unexpected(_) => default,
+		}
+	}
+
+	/// Maps a `Exun<E, U>` to `T` by applying `f` to a contained
+	/// [`Expected`] value, or `default` to a contained [`Unexpected`] value.
+	///
+	/// Unlike [`map_or`], the `default` function is given the
+	/// [`Unexpected`] value.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x: Exun<&str, u32> = Expected("foo");
+	/// assert_eq!(x.map_or_else(|u| u as usize * 2, |e| e.len()), 3);
+	///
+	/// let x: Exun<&str, u32> = Unexpected(13);
+	/// assert_eq!(x.map_or_else(|u| u as usize * 2, |e| e.len()), 26);
+	/// ```
+	///
+	/// [`map_or`]: Self::map_or
+	pub fn map_or_else<T, D: FnOnce(U) -> T, F: FnOnce(E) -> T>(self, default: D, f: F) -> T {
+		match self {
+			Expected(e) => f(e),
+			Unexpected(u) => default(u),
+		}
+	}
+
+	/// Swaps the variants, turning an [`Expected`] value into an
+	/// [`Unexpected`] one, and vice versa.
+	///
+	/// This is useful together with [`map`] and [`map_unexpected`] to change
+	/// which errors are considered recoverable.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x: Exun<&str, i32> = Expected("hello");
+	/// assert_eq!(x.swap(), Unexpected("hello"));
+	///
+	/// let x: Exun<&str, i32> = Unexpected(3);
+	/// assert_eq!(x.swap(), Expected(3));
+	///
+	/// let x: Exun<&str, i32> = Expected("hello");
+	/// assert_eq!(x.swap().swap(), x);
+	///
+	/// let x: Exun<&str, i32> = Unexpected(3);
+	/// assert_eq!(x.swap().swap(), x);
+	/// ```
+	///
+	/// [`map`]: Self::map
+	/// [`map_unexpected`]: Self::map_unexpected
+	#[allow(clippy::missing_const_for_fn)]
+	pub fn swap(self) -> Exun<U, E> {
+		match self {
+			Expected(e) => Unexpected(e),
+			Unexpected(u) => Expected(u),
+		}
+	}
+
+	/// Returns `other` if the value is [`Expected`], otherwise returns the
+	/// [`Unexpected`] value of `self`.
+	///
+	/// Arguments passed to `and` are eagerly evaluated; if you are passing
+	/// the result of a function call, it is recommended to use [`and_then`],
+	/// which is lazily evaluated.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x: Exun<u32, &str> = Expected(2);
+	/// let y: Exun<&str, &str> = Unexpected("late error");
+	/// assert_eq!(x.and(y), Unexpected("late error"));
+	///
+	/// let x: Exun<u32, &str> = Unexpected("early error");
+	/// let y: Exun<&str, &str> = Expected("foo");
+	/// assert_eq!(x.and(y), Unexpected("early error"));
+	///
+	/// let x: Exun<u32, &str> = Unexpected("not a 2");
+	/// let y: Exun<&str, &str> = Unexpected("late error");
+	/// assert_eq!(x.and(y), Unexpected("not a 2"));
+	///
+	/// let x: Exun<u32, &str> = Expected(2);
+	/// let y: Exun<&str, &str> = Expected("different result type");
+	/// assert_eq!(x.and(y), Expected("different result type"));
+	/// ```
+	///
+	/// [`and_then`]: Self::and_then
+	#[allow(clippy::missing_const_for_fn)]
+	pub fn and<F>(self, other: Exun<F, U>) -> Exun<F, U> {
+		match self {
+			Expected(_) => other,
+			Unexpected(u) => Unexpected(u),
+		}
+	}
+
+	/// Calls `op` if the value is [`Expected`], otherwise returns the
+	/// [`Unexpected`] value of `self`.
+	///
+	/// Unlike [`map`], `op` may itself return an [`Unexpected`] value, which
+	/// allows a chain of steps to be short-circuited.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// fn check(code: u32) -> Exun<u32, String> {
+	///     if code < 500 {
+	///         Expected(code)
+	///     } else {
+	///         Unexpected(format!("server error {}", code))
+	///     }
+	/// }
+	///
+	/// let x: Exun<u32, String> = Expected(404);
+	/// assert_eq!(x.and_then(check), Expected(404));
+	///
+	/// let x: Exun<u32, String> = Expected(503);
+	/// assert_eq!(x.and_then(check), Unexpected("server error 503".to_string()));
+	///
+	/// // the second step is never run
+	/// let x: Exun<u32, String> = Expected(503);
+	/// let x = x.and_then(check).and_then(|_| Expected(0));
+	/// assert_eq!(x, Unexpected("server error 503".to_string()));
+	/// ```
+	///
+	/// An [`Unexpected`] value is left untouched:
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x: Exun<u32, &str> = Unexpected("lost connection");
+	/// assert_eq!(x.and_then(|e| Expected(e + 1)), Unexpected("lost connection"));
+	///
+	/// let x: Exun<u32, &str> = Unexpected("lost connection");
+	/// assert_eq!(x.and_then(|_| Unexpected::<u32, _>("other")), Unexpected("lost connection"));
+	/// ```
+	///
+	/// [`map`]: Self::map
+	pub fn and_then<F, G: FnOnce(E) -> Exun<F, U>>(self, op: G) -> Exun<F, U> {
+		match self {
+			Expected(e) => op(e),
+			Unexpected(u) => Unexpected(u),
+		}
+	}
+
+	/// Returns `other` if the value is [`Unexpected`], otherwise returns the
+	/// [`Expected`] value of `self`.
+	///
+	/// Arguments passed to `or` are eagerly evaluated; if you are passing the
+	/// result of a function call, it is recommended to use [`or_else`], which
+	/// is lazily evaluated.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x: Exun<u32, &str> = Expected(2);
+	/// let y: Exun<u32, u8> = Unexpected(13);
+	/// assert_eq!(x.or(y), Expected(2));
+	///
+	/// let x: Exun<u32, &str> = Unexpected("early error");
+	/// let y: Exun<u32, u8> = Expected(2);
+	/// assert_eq!(x.or(y), Expected(2));
+	///
+	/// let x: Exun<u32, &str> = Unexpected("not a 2");
+	/// let y: Exun<u32, u8> = Unexpected(13);
+	/// assert_eq!(x.or(y), Unexpected(13));
+	///
+	/// let x: Exun<u32, &str> = Expected(2);
+	/// let y: Exun<u32, u8> = Expected(100);
+	/// assert_eq!(x.or(y), Expected(2));
+	/// ```
+	///
+	/// [`or_else`]: Self::or_else
+	#[allow(clippy::missing_const_for_fn)]
+	pub fn or<F>(self, other: Exun<E, F>) -> Exun<E, F> {
+		match self {
+			Expected(e) => Expected(e),
+			Unexpected(_) => other,
+		}
+	}
+
+	/// Calls `op` if the value is [`Unexpected`], otherwise returns the
+	/// [`Expected`] value of `self`.
+	///
+	/// This can be used to attempt to recover from an unexpected error. The
+	/// result of `op` replaces the [`Unexpected`] value, and may itself be
+	/// [`Expected`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// fn retry(attempts: u32) -> Exun<&'static str, u32> {
+	///     if attempts < 3 {
+	///         Expected("recovered")
+	///     } else {
+	///         Unexpected(attempts + 1)
+	///     }
+	/// }
+	///
+	/// let x: Exun<&str, u32> = Unexpected(1);
+	/// assert_eq!(x.or_else(retry), Expected("recovered"));
+	///
+	/// let x: Exun<&str, u32> = Unexpected(3);
+	/// assert_eq!(x.or_else(retry), Unexpected(4));
+	///
+	/// // an expected value is passed through untouched
+	/// let x: Exun<&str, u32> = Expected("not found");
+	/// assert_eq!(x.or_else(retry), Expected("not found"));
+	/// ```
+	pub fn or_else<F, G: FnOnce(U) -> Exun<E, F>>(self, op: G) -> Exun<E, F> {
+		match self {
+			Expected(e) => Expected(e),
+			Unexpected(u) => op(u),
+		}
+	}
+
+	/// Calls `f` with a reference to the contained value if [`Expected`].
+	///
+	/// Returns `self` unchanged.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let mut seen = Vec::new();
+	///
+	/// let x: Exun<i32, &str> = Expected(2);
+	/// let x = x.inspect_expected(|e| seen.push(*e));
+	/// assert_eq!(x, Expected(2));
+	///
+	/// let x: Exun<i32, &str> = Unexpected("error");
+	/// let x = x.inspect_expected(|e| seen.push(*e));
+	/// assert_eq!(x, Unexpected("error"));
+	///
+	/// assert_eq!(seen, vec![2]);
+	/// ```
+	#[must_use]
+	pub fn inspect_expected<F: FnOnce(&E)>(self, f: F) -> Self {
+		if let Expected(e) = &self {
+			f(e);
+		}
+
+		self
+	}
+
+	/// Calls `f` with a reference to the contained value if [`Unexpected`].
+	///
+	/// Returns `self` unchanged.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let mut seen = Vec::new();
+	///
+	/// let x: Exun<i32, &str> = Expected(2);
+	/// let x = x.inspect_unexpected(|u| seen.push(*u));
+	/// assert_eq!(x, Expected(2));
+	///
+	/// let x: Exun<i32, &str> = Unexpected("error");
+	/// let x = x.inspect_unexpected(|u| seen.push(*u));
+	/// assert_eq!(x, Unexpected("error"));
+	///
+	/// assert_eq!(seen, vec!["error"]);
+	/// ```
+	#[must_use]
+	pub fn inspect_unexpected<F: FnOnce(&U)>(self, f: F) -> Self {
+		if let Unexpected(u) = &self {
+			f(u);
+		}
+
+		self
+	}
+
+	/// Applies a function to a contained [`Expected`] value, but only if
+	/// `cond` is true.
+	///
+	/// If `cond` is false, or the value is [`Unexpected`], then `self` is
+	/// returned unchanged.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// fn normalize(x: Exun<String, i32>, lowercase: bool) -> Exun<String, i32> {
+	///     x.map_if(lowercase, |e| e.to_lowercase())
+	/// }
+	///
+	/// let x: Exun<String, i32> = Expected("NOT FOUND".to_string());
+	/// assert_eq!(normalize(x.clone(), true), Expected("not found".to_string()));
+	/// assert_eq!(normalize(x, false), Expected("NOT FOUND".to_string()));
+	///
+	/// let x: Exun<String, i32> = Unexpected(13);
+	/// assert_eq!(normalize(x, true), Unexpected(13));
+	/// ```
+	#[must_use]
+	pub fn map_if(self, cond: bool, f: impl FnOnce(E) -> E) -> Self {
+		match self {
+			Expected(e) if cond => Expected(f(e)),
+			other => other,
+		}
+	}
+
+	/// Returns the [`Expected`] value, consuming the `self` value.
+	///
+	/// Because this function may panic, its use is generally discouraged.
+	/// Instead, prefer to use pattern matching and handle the [`Unexpected`]
+	/// case explicitly.
+	///
+	/// # Panics
+	///
+	/// Panics if the value is an [`Unexpected`] value, with a panic message
+	/// including the passed message, and the content of the [`Unexpected`]
+	/// value.
+	///
+	/// # Examples
+	///
+	/// ```should_panic
+	/// use exun::*;
+	///
+	/// let x: Exun<u32, &str> = Exun::Unexpected("error");
+	/// x.expect("Testing expect"); // panics with "testing expect: error"
+	/// ```
+	///
+	/// # Recommended Message Style
+	///
+	/// We recommend that `expect` messages are used to describe the reason you
+	/// *expect* the `Exun` should be `Expected`.
+	///
+	/// ```should_panic
+	/// let path = std::env::var("IMPORTANT_PATH")
+	///     .expect("env variable `IMPORTANT_PATH` should be set by test.sh");
+	/// ```
+	///
+	/// **Hint:** If you're having trouble remembering how to phrase expect
+	/// error messages, remember to focus on the word "should" as in "env
+	/// variable set by blah" or "the given binary should be available and
+	/// executable by the current user".
+	///
+	/// For more detail on expect message styles and the reasoning behind the
+	/// recommendation please refer to the section on
+	/// [Common Message Styles](https://doc.rust-lang.org/stable/std/error/index.html#common-message-styles)
+	/// in the [`std::error`](https://doc.rust-lang.org/stable/std/error/index.html)
+	/// module docs.
+	#[track_caller]
+	pub fn expect(self, msg: &str) -> E
+	where
+		U: Debug,
+	{
+		match self {
+			Self::Expected(e) => e,
+			Self::Unexpected(e) => panic!("{}: {:?}", msg, e),
+		}
+	}
+
+	/// Returns the contained [`Expected`] value, consuming the `self` value.
+	///
+	/// Because this function may panic, its use is generally discouraged.
+	/// Instead, prefer to use pattern matching and handle the [`Unexpected`]
+	/// case explicitly, or call [`unwrap_or`] or [`unwrap_or_else`].
+	///
+	/// # Panics
+	///
+	/// Panics if the value is [`Unexpected`], with an panic message provided
+	/// by the [`Unexpected`]'s value.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x: Exun<u32, &str> = Expected(2);
+	/// assert_eq!(x.unwrap(), 2);
+	/// ```
+	///
+	/// ```should_panic
+	/// use exun::*;
+	///
+	/// let x: Exun<u32, &str> = Unexpected("emergency failure");
+	/// x.unwrap(); // panics with `emergency failure`
+	/// ```
+	///
+	/// [`unwrap_or`]: Self::unwrap_or
+	/// [`unwrap_or_else`]: Self::unwrap_or_else
+	#[track_caller]
+	pub fn unwrap(self) -> E
+	where
+		U: Debug,
+	{
+		match self {
+			Expected(e) => e,
+			Unexpected(u) => panic!("called `Expect::unwrap` on an `Unexpected` value: {:?}", u),
+		}
+	}
+
+	/// Returns the contained [`Unexpected`] value, consuming the `self` value.
+	///
+	/// # Panics
+	///
+	/// Panics if the value is [`Expected`], with an panic message provided by
+	/// the [`Expected`]'s value.
+	///
+	/// # Examples
+	///
+	/// ```should_panic
+	/// use exun::*;
+	///
+	/// let x: Exun<u32, &str> = Expected(2);
+	/// x.unwrap_unexpected(); // panics with `2`
+	/// ```
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x: Exun<u32, &str> = Unexpected("emergency failure");
+	/// assert_eq!(x.unwrap_unexpected(), "emergency failure");
+	/// ```
+	#[track_caller]
+	pub fn unwrap_unexpected(self) -> U
+	where
+		E: Debug,
+	{
+		match self {
+			Expected(e) => panic!(
+				"called `Expect::unwrap_unexpected` on an `Expected` value: {:?}",
+				e
+			),
+			Unexpected(u) => u,
+		}
+	}
+
+	/// Returns the contained [`Unexpected`] value, consuming the `self` value.
+	///
+	/// # Panics
+	///
+	/// Panics if the value is [`Expected`], with a panic message including
+	/// the passed message, and the content of the [`Expected`] value.
+	///
+	/// # Examples
+	///
+	/// ```should_panic
+	/// use exun::*;
+	///
+	/// let x: Exun<u32, &str> = Expected(2);
+	/// x.expect_unexpected("Testing expect_unexpected"); // panics with `Testing expect_unexpected: 2`
+	/// ```
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x: Exun<u32, &str> = Unexpected("emergency failure");
+	/// assert_eq!(x.expect_unexpected("should be unexpected"), "emergency failure");
+	/// ```
+	#[track_caller]
+	pub fn expect_unexpected(self, msg: &str) -> U
+	where
+		E: Debug,
+	{
+		match self {
+			Expected(e) => panic!("{}: {:?}", msg, e),
+			Unexpected(u) => u,
+		}
+	}
+
+	/// Returns the contained [`Expected`] value or a provided default.
+	///
+	/// Arguments passed to `unwrap_or` are eagerly evaluated; if you are
+	/// passing the result of a function call, it is recommended to use
+	/// [`unwrap_or_else`], which is lazily evaluated.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let default = 2;
+	/// let x: Exun<u32, &str> = Expected(9);
+	/// assert_eq!(x.unwrap_or(default), 9);
+	///
+	/// let x: Exun<u32, &str> = Unexpected("error");
+	/// assert_eq!(x.unwrap_or(default), default);
+	/// ```
+	///
+	/// [`unwrap_or_else`]: Self::unwrap_or_else
+	pub fn unwrap_or(self, default: E) -> E {
+		match self {
+			Expected(e) => e,
+			Unexpected(_) => default,
+		}
+	}
+
+	/// Returns a reference to the contained [`Expected`] value or to a
+	/// provided default.
+	///
+	/// This is the borrowing equivalent of [`unwrap_or`], and can be used to
+	/// read the expected value without cloning it.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let default = String::from("default");
+	///
+	/// let x: Exun<String, &str> = Expected(String::from("value"));
+	/// assert_eq!(x.expected_ref_or(&default), "value");
+	///
+	/// let x: Exun<String, &str> = Unexpected("error");
+	/// assert_eq!(x.expected_ref_or(&default), "default");
+	/// ```
+	///
+	/// [`unwrap_or`]: Self::unwrap_or
+	#[allow(clippy::missing_const_for_fn)]
+	pub fn expected_ref_or<'a>(&'a self, default: &'a E) -> &'a E {
+		match self {
+			Expected(e) => e,
+			Unexpected(_) => default,
+		}
+	}
+
+	/// Returns the [`Expected`] value or computes it from a closure.
+	///
+	/// The closure is given the [`Unexpected`] value, so it can be translated
+	/// into an expected value.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// fn count(x: &str) -> usize { x.len() }
+	///
+	/// assert_eq!(Expected(2).unwrap_or_else(count), 2);
+	/// assert_eq!(Unexpected("foo").unwrap_or_else(count), 3);
+	/// ```
+	pub fn unwrap_or_else<F: FnOnce(U) -> E>(self, op: F) -> E {
+		match self {
+			Expected(e) => e,
+			Unexpected(u) => op(u),
+		}
+	}
+
+	/// Returns the contained [`Expected`] value or a default.
+	///
+	/// If the value is [`Expected`], it's returned. Otherwise, the default
+	/// value of `E` is returned.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x: Exun<u32, &str> = Expected(9);
+	/// assert_eq!(x.unwrap_or_default(), 9);
+	///
+	/// let x: Exun<u32, &str> = Unexpected("error");
+	/// assert_eq!(x.unwrap_or_default(), 0);
+	/// ```
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x: Exun<String, i32> = Unexpected(13);
+	/// assert_eq!(x.unwrap_or_default(), String::new());
+	/// ```
+	pub fn unwrap_or_default(self) -> E
+	where
+		E: Default,
+	{
+		match self {
+			Expected(e) => e,
+			Unexpected(_) => E::default(),
+		}
+	}
+
+	/// Returns the contained [`Unexpected`] value or a default.
+	///
+	/// If the value is [`Unexpected`], it's returned. Otherwise, the default
+	/// value of `U` is returned.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x: Exun<&str, u32> = Unexpected(9);
+	/// assert_eq!(x.unwrap_unexpected_or_default(), 9);
+	///
+	/// let x: Exun<&str, u32> = Expected("error");
+	/// assert_eq!(x.unwrap_unexpected_or_default(), 0);
+	/// ```
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x: Exun<i32, String> = Expected(13);
+	/// assert_eq!(x.unwrap_unexpected_or_default(), String::new());
+	///
+	/// let x: Exun<i32, String> = Unexpected("failed".to_string());
+	/// assert_eq!(x.unwrap_unexpected_or_default(), "failed");
+	/// ```
+	pub fn unwrap_unexpected_or_default(self) -> U
+	where
+		U: Default,
+	{
+		match self {
+			Expected(_) => U::default(),
+			Unexpected(u) => u,
+		}
+	}
+
+	/// Returns the [`Expected`] value, or attempts to recover from the
+	/// [`Unexpected`] value using a fallible function.
+	///
+	/// If the value is [`Expected`], then it's returned as [`Ok`]. Otherwise,
+	/// `op` is called with the [`Unexpected`] value, which may either recover
+	/// an expected value, or fail with a new error.
+	///
+	/// Also known as `expected_or_else`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// fn recover(code: i32) -> Result<&'static str, String> {
+	///     match code {
+	///         404 => Ok("not found"),
+	///         code => Err(format!("unknown error code: {code}")),
+	///     }
+	/// }
+	///
+	/// let x: Exun<&str, i32> = Expected("timed out");
+	/// assert_eq!(x.or_try(recover), Ok("timed out"));
+	///
+	/// let x: Exun<&str, i32> = Unexpected(404);
+	/// assert_eq!(x.or_try(recover), Ok("not found"));
+	///
+	/// let x: Exun<&str, i32> = Unexpected(500);
+	/// assert_eq!(x.or_try(recover), Err("unknown error code: 500".to_string()));
+	/// ```
+	pub fn or_try<X, F: FnOnce(U) -> Result<E, X>>(self, op: F) -> Result<E, X> {
+		match self {
+			Expected(e) => Ok(e),
+			Unexpected(u) => op(u),
+		}
+	}
+
+	/// Attempts to recover from an [`Unexpected`] value using each of the
+	/// given strategies, in order.
+	///
+	/// The first strategy is given the original [`Unexpected`] value. If it
+	/// returns an [`Expected`] value, then that is returned, and no more
+	/// strategies are tried. Otherwise, its [`Unexpected`] value is passed to
+	/// the next strategy. If every strategy fails, the [`Unexpected`] value
+	/// from the last one is returned. An [`Expected`] value is returned
+	/// without trying any strategies.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// // tries to find a user's name, given their ID
+	/// fn from_cache(id: u32) -> Exun<&'static str, u32> {
+	///     Unexpected(id)
+	/// }
+	///
+	/// fn from_disk(id: u32) -> Exun<&'static str, u32> {
+	///     match id {
+	///         1 => Expected("Alice"),
+	///         id => Unexpected(id),
+	///     }
+	/// }
+	///
+	/// let x: Exun<&str, u32> = Unexpected(1);
+	/// assert_eq!(x.recover_with(&[from_cache, from_disk]), Expected("Alice"));
+	///
+	/// let x: Exun<&str, u32> = Unexpected(2);
+	/// assert_eq!(x.recover_with(&[from_cache, from_disk]), Unexpected(2));
+	///
+	/// let x: Exun<&str, u32> = Expected("Bob");
+	/// assert_eq!(x.recover_with(&[from_cache, from_disk]), Expected("Bob"));
+	/// ```
+	#[must_use]
+	pub fn recover_with(self, strategies: &[fn(U) -> Self]) -> Self {
+		let mut result = self;
+		for strategy in strategies {
+			result = match result {
+				Expected(e) => return Expected(e),
+				Unexpected(u) => strategy(u),
+			};
+		}
+
+		result
+	}
+
+	/// Converts from `Exun<E, U>` to `Result<Option<E>, U>`, treating only
+	/// the [`Unexpected`] value as a failure.
+	///
+	/// [`Expected`] values become `Ok(Some(e))`, and [`Unexpected`] values
+	/// become `Err(u)`. This lets the try operator propagate only the
+	/// surprises, while still marking the success value as an expected error,
+	/// rather than an ordinary value as a `Result<E, U>` would.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x: Exun<i32, &str> = Expected(2);
+	/// assert_eq!(x.ok_unless_unexpected(), Ok(Some(2)));
+	///
+	/// let x: Exun<i32, &str> = Unexpected("error");
+	/// assert_eq!(x.ok_unless_unexpected(), Err("error"));
+	/// ```
+	///
+	/// Use with the try operator
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// fn foo(x: Exun<i32, &str>) -> Result<i32, &str> {
+	///     let expected = x.ok_unless_unexpected()?;
+	///     Ok(expected.unwrap_or_default())
+	/// }
+	///
+	/// assert_eq!(foo(Expected(2)), Ok(2));
+	/// assert_eq!(foo(Unexpected("error")), Err("error"));
+	/// ```
+	pub fn ok_unless_unexpected(self) -> Result<Option<E>, U> {
+		match self {
+			Expected(e) => Ok(Some(e)),
+			Unexpected(u) => Err(u),
+		}
+	}
+
+	/// Converts from `Result<E, U>` to `Exun<E, U>`.
+	///
+	/// [`Ok`] values become [`Expected`], and [`Err`] values become
+	/// [`Unexpected`]. This is the inverse of [`into_result`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x: Exun<u32, &str> = Exun::from_result(Ok(404));
+	/// assert_eq!(x, Expected(404));
+	///
+	/// let x: Exun<u32, &str> = Exun::from_result(Err("error"));
+	/// assert_eq!(x, Unexpected("error"));
+	/// ```
+	///
+	/// [`into_result`]: Self::into_result
+	#[allow(clippy::missing_const_for_fn)]
+	pub fn from_result(res: Result<E, U>) -> Self {
+		match res {
+			Ok(e) => Expected(e),
+			Err(u) => Unexpected(u),
+		}
+	}
+
+	/// Converts from `Exun<E, U>` to `Result<E, U>`.
+	///
+	/// [`Expected`] values become [`Ok`], and [`Unexpected`] values become
+	/// [`Err`]. This is the inverse of [`from_result`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x: Exun<u32, &str> = Expected(404);
+	/// assert_eq!(x.into_result(), Ok(404));
+	///
+	/// let x: Exun<u32, &str> = Unexpected("error");
+	/// assert_eq!(x.into_result(), Err("error"));
+	///
+	/// let x: Exun<u32, &str> = Unexpected("error");
+	/// assert_eq!(Exun::from_result(x.into_result()), x);
+	///
+	/// let res: Result<u32, &str> = Ok(404);
+	/// assert_eq!(Exun::from_result(res).into_result(), res);
+	/// ```
+	///
+	/// [`from_result`]: Self::from_result
+	#[allow(clippy::missing_const_for_fn)]
+	pub fn into_result(self) -> Result<E, U> {
+		match self {
+			Expected(e) => Ok(e),
+			Unexpected(u) => Err(u),
+		}
+	}
+
+	/// Converts from `Exun<E, U>` to `Result<E, U>`, treating the
+	/// [`Expected`] value as the success.
+	///
+	/// [`Expected`] values become [`Ok`], and [`Unexpected`] values become
+	/// [`Err`]. This is an alias for [`into_result`], which makes it clearer
+	/// which variant becomes [`Ok`]. See [`unexpected_ok`] for the opposite
+	/// conversion.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x: Exun<u32, &str> = Expected(404);
+	/// assert_eq!(x.expected_ok(), Ok(404));
+	///
+	/// let x: Exun<u32, &str> = Unexpected("error");
+	/// assert_eq!(x.expected_ok(), Err("error"));
+	/// ```
+	///
+	/// [`into_result`]: Self::into_result
+	/// [`unexpected_ok`]: Self::unexpected_ok
+	pub fn expected_ok(self) -> Result<E, U> {
+		self.into_result()
+	}
+
+	/// Converts from `Exun<E, U>` to `Result<U, E>`, treating the
+	/// [`Unexpected`] value as the success.
+	///
+	/// [`Unexpected`] values become [`Ok`], and [`Expected`] values become
+	/// [`Err`]. See [`expected_ok`] for the opposite conversion.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x: Exun<u32, &str> = Expected(404);
+	/// assert_eq!(x.unexpected_ok(), Err(404));
+	///
+	/// let x: Exun<u32, &str> = Unexpected("error");
+	/// assert_eq!(x.unexpected_ok(), Ok("error"));
+	/// ```
+	///
+	/// [`expected_ok`]: Self::expected_ok
+	#[allow(clippy::missing_const_for_fn)]
+	pub fn unexpected_ok(self) -> Result<U, E> {
+		match self {
+			Expected(e) => Err(e),
+			Unexpected(u) => Ok(u),
+		}
+	}
+
+	/// Converts from `&Exun<E, U>` to `Result<E, U>` by cloning the contained
+	/// value.
+	///
+	/// [`Expected`] values become [`Ok`], and [`Unexpected`] values become
+	/// [`Err`]. The original `Exun` is left untouched.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x: Exun<String, i32> = Expected("expected".to_string());
+	/// assert_eq!(x.to_result(), Ok("expected".to_string()));
+	///
+	/// let x: Exun<String, i32> = Unexpected(13);
+	/// assert_eq!(x.to_result(), Err(13));
+	/// assert_eq!(x, Unexpected(13));
+	/// ```
+	pub fn to_result(&self) -> Result<E, U>
+	where
+		E: Clone,
+		U: Clone,
+	{
+		match self {
+			Expected(e) => Ok(e.clone()),
+			Unexpected(u) => Err(u.clone()),
+		}
+	}
+
+	/// Converts from `Exun<E, U>` to `Result<T, X>` by applying a function to
+	/// whichever value is contained.
+	///
+	/// [`Expected`] values are passed to `on_expected` to create an [`Ok`],
+	/// and [`Unexpected`] values are passed to `on_unexpected` to create an
+	/// [`Err`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// fn to_result(x: Exun<&str, i32>) -> Result<usize, String> {
+	///     x.into_result_with(|e| e.len(), |u| format!("error code: {u}"))
+	/// }
+	///
+	/// assert_eq!(to_result(Expected("not found")), Ok(9));
+	/// assert_eq!(to_result(Unexpected(13)), Err("error code: 13".to_string()));
+	/// ```
+	pub fn into_result_with<T, X>(
+		self,
+		on_expected: impl FnOnce(E) -> T,
+		on_unexpected: impl FnOnce(U) -> X,
+	) -> Result<T, X> {
+		match self {
+			Expected(e) => Ok(on_expected(e)),
+			Unexpected(u) => Err(on_unexpected(u)),
+		}
+	}
+
+	/// Returns an iterator over the possibly contained [`Expected`] value,
+	/// consuming `self`.
+	///
+	/// The iterator yields one value if the `Exun` is [`Expected`], otherwise
+	/// none.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let errors: Vec<Exun<i32, &str>> = vec![Expected(1), Unexpected("error"), Expected(3)];
+	/// let expected: Vec<i32> = errors.into_iter().flat_map(Exun::into_iter_expected).collect();
+	/// assert_eq!(expected, vec![1, 3]);
+	/// ```
+	pub fn into_iter_expected(self) -> impl Iterator<Item = E> {
+		self.expected().into_iter()
+	}
+
+	/// Returns an iterator over the possibly contained [`Expected`] value.
+	///
+	/// The iterator yields one value if the value is [`Expected`], otherwise
+	/// none.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x: Exun<i32, &str> = Expected(7);
+	/// assert_eq!(x.iter().next(), Some(&7));
+	///
+	/// let x: Exun<i32, &str> = Unexpected("error");
+	/// assert_eq!(x.iter().next(), None);
+	/// ```
+	pub fn iter(&self) -> Iter<'_, E> {
+		Iter {
+			inner: self.as_ref().expected(),
+		}
+	}
+
+	/// Returns a mutable iterator over the possibly contained [`Expected`]
+	/// value.
+	///
+	/// The iterator yields one value if the value is [`Expected`], otherwise
+	/// none.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let mut x: Exun<i32, &str> = Expected(7);
+	/// if let Some(e) = x.iter_mut().next() {
+	///     *e = 40;
+	/// }
+	/// assert_eq!(x, Expected(40));
+	///
+	/// let mut x: Exun<i32, &str> = Unexpected("error");
+	/// assert_eq!(x.iter_mut().next(), None);
+	/// ```
+	pub fn iter_mut(&mut self) -> IterMut<'_, E> {
+		IterMut {
+			inner: self.as_mut().expected(),
+		}
+	}
+
+	/// Converts from `Exun<E, U>` to [`Option<E>`], handling the
+	/// [`Unexpected`] value with a function.
+	///
+	/// If the value is [`Unexpected`], it's passed to `f`, and [`None`] is
+	/// returned. This can be used to log the unexpected error, and then
+	/// continue with the expected error, if there is one.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let mut log = Vec::new();
+	///
+	/// let x: Exun<i32, &str> = Expected(2);
+	/// assert_eq!(x.on_unexpected(|u| log.push(u)), Some(2));
+	///
+	/// let x: Exun<i32, &str> = Unexpected("error");
+	/// assert_eq!(x.on_unexpected(|u| log.push(u)), None);
+	///
+	/// assert_eq!(log, vec!["error"]);
+	/// ```
+	pub fn on_unexpected(self, f: impl FnOnce(U)) -> Option<E> {
+		match self {
+			Expected(e) => Some(e),
+			Unexpected(u) => {
+				f(u);
+				None
+			}
+		}
+	}
+
+	/// Returns the contained [`Expected`] value, or exits the process if the
+	/// value is [`Unexpected`].
+	///
+	/// If the value is [`Unexpected`], it's printed to the standard error,
+	/// and the process exits with the given `code`. This is useful for
+	/// command-line tools, which should report an unexpected error without
+	/// the noise of a panic.
+	///
+	/// Note that this calls [`std::process::exit`], so the stack isn't
+	/// unwound, and destructors won't be run.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x: Exun<u32, &str> = Expected(2);
+	/// assert_eq!(x.expected_or_exit(1), 2);
+	/// ```
+	///
+	/// ```no_run
+	/// use exun::*;
+	///
+	/// let x: Exun<u32, &str> = Unexpected("failure");
+	/// x.expected_or_exit(1); // prints "failure" and exits with code 1
+	/// ```
+	#[cfg(feature = "std")]
+	pub fn expected_or_exit(self, code: i32) -> E
+	where
+		U: Display,
+	{
+		match self {
+			Expected(e) => e,
+			Unexpected(u) => {
+				eprintln!("{}", u);
+				std::process::exit(code)
+			}
+		}
+	}
+
+	/// Returns the [`Expected`] value, or logs the [`Unexpected`] value and
+	/// returns the default value.
+	///
+	/// If the value is [`Unexpected`], it's logged at the error level, with
+	/// the given `target`, and `E::default()` is returned. This is useful
+	/// when an unexpected error shouldn't stop the program, but shouldn't go
+	/// unnoticed either.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::sync::Mutex;
+	///
+	/// use exun::*;
+	/// use log::{Level, Log, Metadata, Record};
+	///
+	/// struct Logger(Mutex<Vec<(Level, String, String)>>);
+	///
+	/// impl Log for Logger {
+	///     fn enabled(&self, _: &Metadata<'_>) -> bool {
+	///         true
+	///     }
+	///
+	///     fn log(&self, record: &Record<'_>) {
+	///         let level = record.level();
+	///         let target = record.target().to_string();
+	///         let message = record.args().to_string();
+	///         self.0.lock().unwrap().push((level, target, message));
+	///     }
+	///
+	///     fn flush(&self) {}
+	/// }
+	///
+	/// static LOGGER: Logger = Logger(Mutex::new(Vec::new()));
+	/// log::set_logger(&LOGGER).unwrap();
+	/// log::set_max_level(log::LevelFilter::Error);
+	///
+	/// let x: Exun<u32, &str> = Expected(2);
+	/// assert_eq!(x.or_log_default("config"), 2);
+	/// assert!(LOGGER.0.lock().unwrap().is_empty());
+	///
+	/// let x: Exun<u32, &str> = Unexpected("file not found");
+	/// assert_eq!(x.or_log_default("config"), 0);
+	/// assert_eq!(
+	///     *LOGGER.0.lock().unwrap(),
+	///     [(Level::Error, "config".to_string(), "file not found".to_string())]
+	/// );
+	/// ```
+	#[cfg(feature = "log")]
+	pub fn or_log_default(self, target: &str) -> E
+	where
+		E: Default,
+		U: Display,
+	{
+		match self {
+			Expected(e) => e,
+			Unexpected(u) => {
+				log::error!(target: target, "{}", u);
+				E::default()
+			}
+		}
+	}
+
+	/// Converts from `Exun<E, U>` to [`nom::Err<E>`].
+	///
+	/// Parsers using `nom` distinguish between recoverable errors, which
+	/// allow other branches to be tried, and unrecoverable failures. These
+	/// map naturally onto an `Exun`:
+	///
+	/// * [`Expected`] values become [`nom::Err::Error`], which can be
+	///   backtracked from.
+	/// * [`Unexpected`] values become [`nom::Err::Failure`], which are fatal.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x: Exun<&str, &str> = Expected("expected digit");
+	/// assert_eq!(x.into_nom_err(), nom::Err::Error("expected digit"));
+	///
+	/// let x: Exun<&str, &str> = Unexpected("unterminated string");
+	/// assert_eq!(x.into_nom_err(), nom::Err::Failure("unterminated string"));
+	/// ```
+	#[cfg(feature = "nom")]
+	pub fn into_nom_err(self) -> nom::Err<E>
+	where
+		U: Into<E>,
+	{
+		match self {
+			Expected(e) => nom::Err::Error(e),
+			Unexpected(u) => nom::Err::Failure(u.into()),
+		}
+	}
+
+	/// Converts from `Exun<E, U>` to [`RawUnexpected`], wrapping whichever
+	/// error is contained.
+	///
+	/// This discards the distinction between the expected and unexpected
+	/// errors. Converting with [`From`] or the try operator wraps the `Exun`
+	/// itself instead, because `Exun<E, U>` implements [`Error`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::num::ParseIntError;
+	///
+	/// use exun::*;
+	///
+	/// let parse_error = "a".parse::<u32>().unwrap_err();
+	///
+	/// let x: Exun<core::fmt::Error, ParseIntError> = Expected(core::fmt::Error);
+	/// let error = x.into_raw_unexpected();
+	/// assert!(error.source().unwrap().is::<core::fmt::Error>());
+	///
+	/// let x: Exun<core::fmt::Error, ParseIntError> = Unexpected(parse_error.clone());
+	/// let error = x.into_raw_unexpected();
+	/// assert_eq!(error.source().unwrap().downcast_ref(), Some(&parse_error));
+	/// assert_eq!(error.downcast::<ParseIntError>().unwrap(), parse_error);
+	/// ```
+	#[cfg(any(feature = "std", all(feature = "alloc", feature = "core-error")))]
+	pub fn into_raw_unexpected(self) -> RawUnexpected
+	where
+		E: Error + Send + Sync + 'static,
+		U: Error + Send + Sync + 'static,
+	{
+		match self {
+			Expected(e) => RawUnexpected::new(e),
+			Unexpected(u) => RawUnexpected::new(u),
+		}
+	}
+
+	/// Converts from `Exun<E, U>` to [`UnexpectedError`], wrapping whichever
+	/// error is contained.
+	///
+	/// This discards the distinction between the expected and unexpected
+	/// errors, for when it no longer matters. The original error can be
+	/// recovered using [`source`](Error::source).
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::error::Error;
+	/// use std::num::ParseIntError;
+	///
+	/// use exun::*;
+	///
+	/// let parse_error = "a".parse::<u32>().unwrap_err();
+	///
+	/// let x: Exun<core::fmt::Error, ParseIntError> = Expected(core::fmt::Error);
+	/// let error = x.into_unexpected_error();
+	/// assert!(error.source().unwrap().is::<core::fmt::Error>());
+	///
+	/// let x: Exun<core::fmt::Error, ParseIntError> = Unexpected(parse_error.clone());
+	/// let error = x.into_unexpected_error();
+	/// assert_eq!(error.source().unwrap().downcast_ref(), Some(&parse_error));
+	/// ```
+	#[cfg(any(feature = "std", all(feature = "alloc", feature = "core-error")))]
+	pub fn into_unexpected_error(self) -> UnexpectedError
+	where
+		E: Error + Send + Sync + 'static,
+		U: Error + Send + Sync + 'static,
+	{
+		match self {
+			Expected(e) => UnexpectedError::new(e),
+			Unexpected(u) => UnexpectedError::new(u),
+		}
+	}
+}
+
+impl<E> Exun<E, RawUnexpected> {
+	/// Returns the [`Expected`] value, consuming the `self` value.
+	///
+	/// This works like [`expect`], except that the panic message includes the
+	/// full chain of errors which caused the [`RawUnexpected`], rather than
+	/// just its top-level message.
+	///
+	/// # Panics
+	///
+	/// Panics if the value is an [`Unexpected`] value, with a panic message
+	/// including the passed message, and each error in the chain of the
+	/// [`Unexpected`] value.
+	///
+	/// # Examples
+	///
+	/// ```should_panic
+	/// use exun::*;
+	///
+	/// let x: Exun<u32, RawUnexpected> = Unexpected(RawUnexpected::none());
+	/// x.expect_chain("Testing expect_chain"); // panics with "Testing expect_chain: Called `unexpect` on a `None` value"
+	/// ```
+	///
+	/// Every error in the chain is included in the message:
+	///
+	/// ```
+	/// use std::error::Error;
+	/// use std::fmt::{self, Display};
+	/// use std::panic::{self, AssertUnwindSafe};
+	///
+	/// use exun::*;
+	///
+	/// #[derive(Debug)]
+	/// struct Inner;
+	///
+	/// impl Display for Inner {
+	///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+	///         write!(f, "inner failure")
+	///     }
+	/// }
+	///
+	/// impl Error for Inner {}
+	///
+	/// #[derive(Debug)]
+	/// struct Outer(Inner);
+	///
+	/// impl Display for Outer {
+	///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+	///         write!(f, "outer failure")
+	///     }
+	/// }
+	///
+	/// impl Error for Outer {
+	///     fn source(&self) -> Option<&(dyn Error + 'static)> {
+	///         Some(&self.0)
+	///     }
+	/// }
+	///
+	/// # #[cfg(feature = "std")] {
+	/// let x: Expect<u32> = Unexpected(RawUnexpected::new(Outer(Inner)));
+	/// let payload = panic::catch_unwind(AssertUnwindSafe(|| x.expect_chain("loading"))).unwrap_err();
+	/// assert_eq!(
+	///     payload.downcast_ref::<String>().unwrap(),
+	///     "loading: outer failure: inner failure"
+	/// );
+	/// # }
+	/// ```
+	///
+	/// [`expect`]: Self::expect
+	#[track_caller]
+	pub fn expect_chain(self, msg: &str) -> E {
+		match self {
+			Expected(e) => e,
+			Unexpected(u) => panic!("{}: {}", msg, DisplayChain(&u)),
+		}
+	}
+}
+
+impl<T, X, U> Exun<Result<T, X>, U> {
+	/// Returns the error inside of an [`Expected`] [`Result`], if there is
+	/// one.
+	///
+	/// This returns [`None`] if the value is [`Unexpected`], or if the
+	/// [`Expected`] value is [`Ok`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x: Exun<Result<u32, &str>, i32> = Expected(Err("inner error"));
+	/// assert_eq!(x.expected_inner_err(), Some("inner error"));
+	///
+	/// let x: Exun<Result<u32, &str>, i32> = Expected(Ok(2));
+	/// assert_eq!(x.expected_inner_err(), None);
+	///
+	/// let x: Exun<Result<u32, &str>, i32> = Unexpected(13);
+	/// assert_eq!(x.expected_inner_err(), None);
+	/// ```
+	#[allow(clippy::missing_const_for_fn)]
+	pub fn expected_inner_err(self) -> Option<X> {
+		match self {
+			Expected(Err(x)) => Some(x),
+			Expected(Ok(_)) | Unexpected(_) => None,
+		}
+	}
+}
+
+impl<E, U> Exun<Exun<E, U>, U> {
+	/// Converts from `Exun<Exun<E, U>, U>` to `Exun<E, U>`.
+	///
+	/// This removes one level of nesting at a time.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x: Exun<Exun<&str, u32>, u32> = Expected(Expected("not found"));
+	/// assert_eq!(x.flatten(), Expected("not found"));
+	///
+	/// let x: Exun<Exun<&str, u32>, u32> = Expected(Unexpected(6));
+	/// assert_eq!(x.flatten(), Unexpected(6));
+	///
+	/// let x: Exun<Exun<&str, u32>, u32> = Unexpected(13);
+	/// assert_eq!(x.flatten(), Unexpected(13));
+	/// ```
+	#[allow(clippy::missing_const_for_fn)]
+	pub fn flatten(self) -> Exun<E, U> {
+		match self {
+			Expected(inner) => inner,
+			Unexpected(u) => Unexpected(u),
+		}
+	}
+}
+
+impl<E, U> Exun<Option<E>, U> {
+	/// Transposes an `Exun` of an [`Option`] into an [`Option`] of an `Exun`.
+	///
+	/// `Expected(None)` will be mapped to [`None`]. `Expected(Some(e))` and
+	/// `Unexpected(u)` will be mapped to `Some(Expected(e))` and
+	/// `Some(Unexpected(u))`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x: Exun<Option<u32>, &str> = Expected(Some(404));
+	/// assert_eq!(x.transpose(), Some(Expected(404)));
+	///
+	/// let x: Exun<Option<u32>, &str> = Expected(None);
+	/// assert_eq!(x.transpose(), None);
+	///
+	/// let x: Exun<Option<u32>, &str> = Unexpected("error");
+	/// assert_eq!(x.transpose(), Some(Unexpected("error")));
+	/// ```
+	///
+	/// This is the inverse of transposing an [`Option`] of an `Exun`:
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// fn inverse(x: Option<Exun<u32, &str>>) -> Exun<Option<u32>, &str> {
+	///     match x {
+	///         Some(Expected(e)) => Expected(Some(e)),
+	///         Some(Unexpected(u)) => Unexpected(u),
+	///         None => Expected(None),
+	///     }
+	/// }
+	///
+	/// for x in [Expected(Some(404)), Expected(None), Unexpected("error")].iter() {
+	///     assert_eq!(inverse(x.transpose()), *x);
+	/// }
+	/// ```
+	#[allow(clippy::missing_const_for_fn)]
+	pub fn transpose(self) -> Option<Exun<E, U>> {
+		match self {
+			Expected(Some(e)) => Some(Expected(e)),
+			Expected(None) => None,
+			Unexpected(u) => Some(Unexpected(u)),
+		}
+	}
+}
+
+impl<E, U> Exun<&E, &U> {
+	/// Maps an `Exun<&E, &U>` to an `Exun<E, U>` by cloning the contents.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x: Exun<String, u32> = Expected("not found".to_string());
+	/// assert_eq!(x.as_ref().cloned(), x);
+	///
+	/// let x: Exun<String, u32> = Unexpected(13);
+	/// assert_eq!(x.as_ref().cloned(), Unexpected(13));
+	/// ```
+	#[must_use]
+	pub fn cloned(self) -> Exun<E, U>
+	where
+		E: Clone,
+		U: Clone,
+	{
+		match self {
+			Expected(e) => Expected(e.clone()),
+			Unexpected(u) => Unexpected(u.clone()),
+		}
+	}
+
+	/// Maps an `Exun<&E, &U>` to an `Exun<E, U>` by copying the contents.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x: Exun<u32, char> = Expected(404);
+	/// assert_eq!(x.as_ref().copied(), Expected(404));
+	///
+	/// let x: Exun<u32, char> = Unexpected('e');
+	/// assert_eq!(x.as_ref().copied(), Unexpected('e'));
+	/// ```
+	#[must_use]
+	pub fn copied(self) -> Exun<E, U>
+	where
+		E: Copy,
+		U: Copy,
+	{
+		match self {
+			Expected(&e) => Expected(e),
+			Unexpected(&u) => Unexpected(u),
+		}
+	}
+}
+
+impl<T> Exun<T, T> {
+	/// Returns the contained value, regardless of whether it is
+	/// [`Expected`] or [`Unexpected`].
+	///
+	/// This is only available when both sides of the `Exun` are the same
+	/// type. It can be used to collapse an `Exun` once both sides have been
+	/// normalized into a common error type.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x: Exun<i32, i32> = Expected(2);
+	/// assert_eq!(x.into_inner(), 2);
+	///
+	/// let x: Exun<i32, i32> = Unexpected(13);
+	/// assert_eq!(x.into_inner(), 13);
+	/// ```
+	#[allow(clippy::missing_const_for_fn)]
+	pub fn into_inner(self) -> T {
+		match self {
+			Expected(t) | Unexpected(t) => t,
+		}
+	}
+
+	/// Compares the contained values of two `Exun`s, regardless of whether
+	/// they are [`Expected`] or [`Unexpected`].
+	///
+	/// This is only available when both sides of the `Exun` are the same
+	/// type. It's useful for assertions which don't care about how a value was
+	/// classified.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x: Exun<i32, i32> = Expected(2);
+	/// assert!(x.value_eq(&Expected(2)));
+	/// assert!(x.value_eq(&Unexpected(2)));
+	/// assert!(!x.value_eq(&Expected(3)));
+	/// assert!(!x.value_eq(&Unexpected(3)));
+	/// ```
+	pub fn value_eq(&self, other: &Self) -> bool
+	where
+		T: PartialEq,
+	{
+		let a = match self {
+			Expected(a) | Unexpected(a) => a,
+		};
+		let b = match other {
+			Expected(b) | Unexpected(b) => b,
+		};
+
+		a == b
+	}
+}
+
+impl<E> Exun<E, Infallible> {
+	/// Returns the contained [`Expected`] value, but never panics.
+	///
+	/// Unlike [`unwrap`], this method is known to never panic, because the
+	/// [`Unexpected`] type is [`Infallible`], so it can't exist. It also
+	/// doesn't require the unexpected type to implement [`Debug`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use core::convert::Infallible;
+	///
+	/// use exun::*;
+	///
+	/// let x: Exun<u32, Infallible> = Expected(2);
+	/// assert_eq!(x.into_expected_infallible(), 2);
+	/// ```
+	///
+	/// [`unwrap`]: Self::unwrap
+	#[allow(clippy::missing_const_for_fn)]
+	pub fn into_expected_infallible(self) -> E {
+		match self {
+			Expected(e) => e,
+			Unexpected(u) => match u {},
+		}
+	}
+}
+
+impl<E, U> IntoIterator for Exun<E, U> {
+	type Item = E;
+	type IntoIter = IntoIter<E>;
+
+	/// Returns a consuming iterator over the possibly contained [`Expected`]
+	/// value.
+	///
+	/// The iterator yields one value if the value is [`Expected`], otherwise
+	/// none.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x: Exun<i32, &str> = Expected(5);
+	/// let v: Vec<i32> = x.into_iter().collect();
+	/// assert_eq!(v, [5]);
+	///
+	/// let x: Exun<i32, &str> = Unexpected("nothing!");
+	/// let v: Vec<i32> = x.into_iter().collect();
+	/// assert_eq!(v, []);
+	/// ```
+	fn into_iter(self) -> IntoIter<E> {
+		IntoIter {
+			inner: self.expected(),
+		}
+	}
+}
+
+impl<'a, E, U> IntoIterator for &'a Exun<E, U> {
+	type Item = &'a E;
+	type IntoIter = Iter<'a, E>;
+
+	fn into_iter(self) -> Iter<'a, E> {
+		self.iter()
+	}
+}
+
+impl<'a, E, U> IntoIterator for &'a mut Exun<E, U> {
+	type Item = &'a mut E;
+	type IntoIter = IterMut<'a, E>;
+
+	fn into_iter(self) -> IterMut<'a, E> {
+		self.iter_mut()
+	}
+}
+
+impl<E, U, V: FromIterator<E>> FromIterator<Exun<E, U>> for Exun<V, U> {
+	/// Takes each element in the iterator. If it is [`Unexpected`], no
+	/// further elements are taken, and the [`Unexpected`] value is returned.
+	/// Otherwise, a container with the values of each [`Expected`] is
+	/// returned.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let v: Vec<Exun<i32, &str>> = vec![Expected(1), Expected(2), Expected(3)];
+	/// let x: Exun<Vec<i32>, &str> = v.into_iter().collect();
+	/// assert_eq!(x, Expected(vec![1, 2, 3]));
+	///
+	/// let mut taken = 0;
+	/// let v: Vec<Exun<i32, &str>> = vec![Expected(1), Unexpected("oops"), Expected(3)];
+	/// let x: Exun<Vec<i32>, &str> = v
+	///     .into_iter()
+	///     .inspect(|_| taken += 1)
+	///     .collect();
+	/// assert_eq!(x, Unexpected("oops"));
+	/// assert_eq!(taken, 2);
+	/// ```
+	fn from_iter<I: IntoIterator<Item = Exun<E, U>>>(iter: I) -> Self {
+		Self::from_result(iter.into_iter().map(Exun::into_result).collect())
+	}
+}
+
+impl<E, U, T: Sum<E>> Sum<Exun<E, U>> for Exun<T, U> {
+	/// Takes each element in the iterator. If it is [`Unexpected`], no
+	/// further elements are taken, and the [`Unexpected`] value is returned.
+	/// Otherwise, the sum of all of the [`Expected`] values is returned.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let v: Vec<Exun<i32, &str>> = vec![Expected(1), Expected(2), Expected(3)];
+	/// let x: Exun<i32, &str> = v.into_iter().sum();
+	/// assert_eq!(x, Expected(6));
+	///
+	/// let v: Vec<Exun<i32, &str>> = vec![Expected(1), Unexpected("oops"), Expected(3)];
+	/// let x: Exun<i32, &str> = v.into_iter().sum();
+	/// assert_eq!(x, Unexpected("oops"));
+	/// ```
+	fn sum<I: Iterator<Item = Exun<E, U>>>(iter: I) -> Self {
+		Self::from_result(iter.map(Exun::into_result).sum())
+	}
+}
+
+impl<E, U, T: Product<E>> Product<Exun<E, U>> for Exun<T, U> {
+	/// Takes each element in the iterator. If it is [`Unexpected`], no
+	/// further elements are taken, and the [`Unexpected`] value is returned.
+	/// Otherwise, the product of all of the [`Expected`] values is returned.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let v: Vec<Exun<i32, &str>> = vec![Expected(2), Expected(3), Expected(4)];
+	/// let x: Exun<i32, &str> = v.into_iter().product();
+	/// assert_eq!(x, Expected(24));
+	///
+	/// let v: Vec<Exun<i32, &str>> = vec![Expected(2), Unexpected("oops"), Expected(4)];
+	/// let x: Exun<i32, &str> = v.into_iter().product();
+	/// assert_eq!(x, Unexpected("oops"));
+	/// ```
+	fn product<I: Iterator<Item = Exun<E, U>>>(iter: I) -> Self {
+		Self::from_result(iter.map(Exun::into_result).product())
+	}
+}
+
+/// An iterator over a reference to the [`Expected`] value of an [`Exun`].
+///
+/// The iterator yields one value if the value is [`Expected`], otherwise
+/// none.
+///
+/// This is created by [`Exun::iter`].
+///
+/// # Examples
+///
+/// ```
+/// use exun::*;
+///
+/// let x: Exun<i32, &str> = Expected(3);
+/// let mut total = 0;
+/// for e in &x {
+///     total += e;
+/// }
+/// assert_eq!(total, 3);
+///
+/// let x: Exun<i32, &str> = Unexpected("error");
+/// let mut iter = x.iter();
+/// assert_eq!(iter.len(), 0);
+/// assert_eq!(iter.next_back(), None);
+/// ```
+#[derive(Debug)]
+pub struct Iter<'a, E> {
+	inner: Option<&'a E>,
+}
+
+impl<'a, E> Iterator for Iter<'a, E> {
+	type Item = &'a E;
+
+	#[inline]
+	fn next(&mut self) -> Option<&'a E> {
+		self.inner.take()
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let n = usize::from(self.inner.is_some());
+		(n, Some(n))
+	}
+}
+
+impl<'a, E> DoubleEndedIterator for Iter<'a, E> {
+	#[inline]
+	fn next_back(&mut self) -> Option<&'a E> {
+		self.inner.take()
+	}
+}
+
+impl<E> ExactSizeIterator for Iter<'_, E> {}
+
+impl<E> FusedIterator for Iter<'_, E> {}
+
+impl<E> Clone for Iter<'_, E> {
+	fn clone(&self) -> Self {
+		Self { inner: self.inner }
+	}
+}
+
+/// An iterator over a mutable reference to the [`Expected`] value of an
+/// [`Exun`].
+///
+/// The iterator yields one value if the value is [`Expected`], otherwise
+/// none.
+///
+/// This is created by [`Exun::iter_mut`].
+///
+/// # Examples
+///
+/// ```
+/// use exun::*;
+///
+/// let mut x: Exun<i32, &str> = Expected(3);
+/// for e in &mut x {
+///     *e *= 2;
+/// }
+/// assert_eq!(x, Expected(6));
+///
+/// let mut x: Exun<i32, &str> = Unexpected("error");
+/// let mut iter = x.iter_mut();
+/// assert_eq!(iter.len(), 0);
+/// assert_eq!(iter.next_back(), None);
+/// ```
+#[derive(Debug)]
+pub struct IterMut<'a, E> {
+	inner: Option<&'a mut E>,
+}
+
+impl<'a, E> Iterator for IterMut<'a, E> {
+	type Item = &'a mut E;
+
+	#[inline]
+	fn next(&mut self) -> Option<&'a mut E> {
+		self.inner.take()
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let n = usize::from(self.inner.is_some());
+		(n, Some(n))
+	}
+}
+
+impl<'a, E> DoubleEndedIterator for IterMut<'a, E> {
+	#[inline]
+	fn next_back(&mut self) -> Option<&'a mut E> {
+		self.inner.take()
+	}
+}
+
+impl<E> ExactSizeIterator for IterMut<'_, E> {}
+
+impl<E> FusedIterator for IterMut<'_, E> {}
+
+/// An iterator over the [`Expected`] value of an [`Exun`].
+///
+/// The iterator yields one value if the value is [`Expected`], otherwise
+/// none.
+///
+/// This is created by the [`IntoIterator`] implementation of [`Exun`].
+///
+/// # Examples
+///
+/// ```
+/// use exun::*;
+///
+/// let x: Exun<i32, &str> = Expected(3);
+/// let mut iter = x.into_iter();
+/// assert_eq!(iter.len(), 1);
+/// assert_eq!(iter.next_back(), Some(3));
+/// assert_eq!(iter.next(), None);
+///
+/// let mut v = vec![1, 2];
+/// v.extend(Exun::<i32, &str>::Expected(3));
+/// v.extend(Exun::<i32, &str>::Unexpected("error"));
+/// assert_eq!(v, [1, 2, 3]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct IntoIter<E> {
+	inner: Option<E>,
+}
+
+impl<E> Iterator for IntoIter<E> {
+	type Item = E;
+
+	#[inline]
+	fn next(&mut self) -> Option<E> {
+		self.inner.take()
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let n = usize::from(self.inner.is_some());
+		(n, Some(n))
+	}
+}
+
+impl<E> DoubleEndedIterator for IntoIter<E> {
+	#[inline]
+	fn next_back(&mut self) -> Option<E> {
+		self.inner.take()
+	}
+}
+
+impl<E> ExactSizeIterator for IntoIter<E> {}
+
+impl<E> FusedIterator for IntoIter<E> {}
+
+/// Returns [`Expected(())`] if `cond` is true, or an [`Unexpected`] value
+/// created by `err` otherwise.
+///
+/// This is a small building block for checking preconditions, similar to
+/// [`bool::then`].
+///
+/// # Examples
+///
+/// ```
+/// use exun::*;
+///
+/// fn check_len(list: &[i32]) -> Exun<(), String> {
+///     guard(!list.is_empty(), || "the list should never be empty".to_string())
+/// }
+///
+/// assert_eq!(check_len(&[1, 2, 3]), Expected(()));
+/// assert_eq!(
+///     check_len(&[]),
+///     Unexpected("the list should never be empty".to_string())
+/// );
+/// ```
+///
+/// [`Expected(())`]: Expected
+pub fn guard<U>(cond: bool, err: impl FnOnce() -> U) -> Exun<(), U> {
+	if cond {
+		Expected(())
+	} else {
+		Unexpected(err())
+	}
+}
+
+/// Counts the number of [`Expected`] and [`Unexpected`] values in an
+/// iterator.
+///
+/// The counts are returned as `(expected, unexpected)`.
+///
+/// # Examples
+///
+/// ```
+/// use exun::*;
+///
+/// let items: Vec<Exun<i32, &str>> = vec![Expected(1), Unexpected("error"), Expected(3)];
+/// assert_eq!(count_outcomes(items), (2, 1));
+///
+/// let items: Vec<Exun<i32, &str>> = Vec::new();
+/// assert_eq!(count_outcomes(items), (0, 0));
+/// ```
+pub fn count_outcomes<E, U, I>(iter: I) -> (usize, usize)
+where
+	I: IntoIterator<Item = Exun<E, U>>,
+{
+	iter.into_iter()
+		.fold((0, 0), |(expected, unexpected), item| match item {
+			Expected(_) => (expected + 1, unexpected),
+			Unexpected(_) => (expected, unexpected + 1),
+		})
+}
+
+/// Applies a stateful function to each [`Expected`] value of an iterator,
+/// collecting the results, and stopping at the first [`Unexpected`] value.
+///
+/// `f` is given a mutable reference to the state, which starts as `init`,
+/// and the next expected value. If either the iterator or `f` produces an
+/// [`Unexpected`] value, then it is returned immediately, and no more items
+/// are processed. Otherwise, every result is collected into a [`Vec`].
+///
+/// # Examples
+///
+/// ```
+/// use exun::*;
+///
+/// // number each line, but stop if a line is unexpectedly empty
+/// fn number(lines: Vec<Exun<&str, String>>) -> Exun<Vec<String>, String> {
+///     scan_expected(lines, 1, |line_number, line| {
+///         if line.is_empty() {
+///             return Unexpected(format!("line {} is empty", line_number));
+///         }
+///
+///         let numbered = format!("{}: {}", line_number, line);
+///         *line_number += 1;
+///         Expected(numbered)
+///     })
+/// }
+///
+/// assert_eq!(
+///     number(vec![Expected("foo"), Expected("bar")]),
+///     Expected(vec!["1: foo".to_string(), "2: bar".to_string()])
+/// );
+/// assert_eq!(
+///     number(vec![Expected("foo"), Expected(""), Expected("bar")]),
+///     Unexpected("line 2 is empty".to_string())
+/// );
+/// assert_eq!(
+///     number(vec![Expected("foo"), Unexpected("io error".to_string())]),
+///     Unexpected("io error".to_string())
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+pub fn scan_expected<E, U, T, S, I, F>(iter: I, init: S, mut f: F) -> Exun<Vec<T>, U>
+where
+	I: IntoIterator<Item = Exun<E, U>>,
+	F: FnMut(&mut S, E) -> Exun<T, U>,
+{
+	let mut state = init;
+	let mut results = Vec::new();
+	for item in iter {
+		let expected = match item {
+			Expected(e) => e,
+			Unexpected(u) => return Unexpected(u),
+		};
+
+		match f(&mut state, expected) {
+			Expected(t) => results.push(t),
+			Unexpected(u) => return Unexpected(u),
+		}
+	}
+
+	Expected(results)
+}
+
+/// The progress made before an [`Unexpected`] value was found.
+///
+/// This is created by [`collect_until_unexpected`].
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PartialExun<T, U> {
+	/// The [`Expected`] values found before the first [`Unexpected`] value
+	pub completed: Vec<T>,
+	/// The first [`Unexpected`] value, if there was one
+	pub error: Option<U>,
+}
+
+/// Collects the [`Expected`] values of an iterator until the first
+/// [`Unexpected`] value is found.
+///
+/// Unlike collecting into an `Exun`, this keeps the values which were
+/// collected before the [`Unexpected`] value. No items are taken from the
+/// iterator after the first [`Unexpected`] value.
+///
+/// # Examples
+///
+/// ```
+/// use exun::*;
+///
+/// let items: Vec<Exun<i32, &str>> = vec![Expected(1), Expected(2), Unexpected("error"), Expected(4)];
+/// let partial = collect_until_unexpected(items);
+/// assert_eq!(partial.completed, vec![1, 2]);
+/// assert_eq!(partial.error, Some("error"));
+///
+/// let items: Vec<Exun<i32, &str>> = vec![Expected(1), Expected(2)];
+/// let partial = collect_until_unexpected(items);
+/// assert_eq!(partial.completed, vec![1, 2]);
+/// assert_eq!(partial.error, None);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn collect_until_unexpected<T, U, I>(iter: I) -> PartialExun<T, U>
+where
+	I: IntoIterator<Item = Exun<T, U>>,
+{
+	let mut completed = Vec::new();
+	for item in iter {
+		match item {
+			Expected(t) => completed.push(t),
+			Unexpected(u) => {
+				return PartialExun {
+					completed,
+					error: Some(u),
+				}
+			}
+		}
+	}
+
+	PartialExun {
+		completed,
+		error: None,
+	}
+}
+
+/// Groups unexpected values by their [`Display`] output, counting how many
+/// times each message occurs.
+///
+/// The messages are returned in the order they first appear. This is useful
+/// for summarizing the failures of a batch operation in a log, and doesn't
+/// require the values to implement [`Eq`] or [`Hash`].
+///
+/// # Examples
+///
+/// ```
+/// use exun::dedup_unexpected;
+///
+/// let failures = vec!["timed out", "disk full", "timed out", "timed out"];
+/// assert_eq!(
+///     dedup_unexpected(failures),
+///     vec![("timed out".to_string(), 3), ("disk full".to_string(), 1)]
+/// );
+///
+/// let failures: Vec<&str> = Vec::new();
+/// assert!(dedup_unexpected(failures).is_empty());
+/// ```
+///
+/// [`Hash`]: core::hash::Hash
+#[cfg(feature = "alloc")]
+pub fn dedup_unexpected<U: Display, I: IntoIterator<Item = U>>(iter: I) -> Vec<(String, usize)> {
+	// the map finds the index of a message, and the vec keeps the order
+	let mut indices: BTreeMap<String, usize> = BTreeMap::new();
+	let mut counts: Vec<(String, usize)> = Vec::new();
+	for unexpected in iter {
+		let message = unexpected.to_string();
+		if let Some(&index) = indices.get(&message) {
+			counts[index].1 += 1;
+		} else {
+			indices.insert(message.clone(), counts.len());
+			counts.push((message, 1));
+		}
+	}
+
+	counts
 }
@@ -11,25 +11,25 @@
 //! keep your unexpected errors, and don't worry about them until later.
 //!
 //! * This crate works in `no-std`. Some extra features come if `alloc` or `std` is
-//! used.
+//!   used.
 //!
 //! * [`Exun`] is an error type. It'll hold on to your [`Unexpected`] error if you have
-//! one, so you can figure out what to do with it later. If the error is
-//! [`Expected`], then it'll hold onto that too.
+//!   one, so you can figure out what to do with it later. If the error is
+//!   [`Expected`], then it'll hold onto that too.
 //!
 //! * [`RawUnexpected`] bottles up all of your unexpected errors. There's also
-//! [`UnexpectedError`], which implements [`Error`].
+//!   [`UnexpectedError`], which implements [`Error`].
 //!
 //! * [`Expect`] is a type alias for [`Exun<E, RawUnexpected>`].
 //!
 //! * Clearly mark errors that you don't expect to occur by calling
-//! [`Result::unexpect`]. If the error type doesn't implement `Error`, you can
-//! still use [`Result::unexpect_msg`], as long as it implements
-//! `Debug + Display + Send + Sync + 'static`.
+//!   [`Result::unexpect`]. If the error type doesn't implement `Error`, you can
+//!   still use [`Result::unexpect_msg`], as long as it implements
+//!   `Debug + Display + Send + Sync + 'static`.
 //!
 //! ## Usage
 //!
-//! The only pre-requisite is Rust 1.41.1.
+//! The only pre-requisite is Rust 1.46.0.
 //!
 //! For standard features:
 //!
@@ -42,13 +42,13 @@
 //! The following features are enabled by default:
 //!
 //! * `std`: This automatically enables `alloc`. It's used for the standard
-//! library's [`Error`] type. Using this type allows more errors to be converted
-//! into [`Exun`] and [`RawUnexpected`] errors automatically, and it's needed for
-//! [`Result::unexpect`].
+//!   library's [`Error`] type. Using this type allows more errors to be converted
+//!   into [`Exun`] and [`RawUnexpected`] errors automatically, and it's needed for
+//!   [`Result::unexpect`].
 //!
 //! * `alloc`: This is needed for `RawUnexpected` and `UnexpectedError` to hold
-//! string messages. This can be done with `Result::unexpect_msg`. Without this,
-//! only the equivalent of `Result::unexpect_none` can be constructed.
+//!   string messages. This can be done with `Result::unexpect_msg`. Without this,
+//!   only the equivalent of `Result::unexpect_none` can be constructed.
 //!
 //! To disable these features:
 //!
@@ -66,6 +66,34 @@
 //! exun = { version = "0.2", default-features = false, features = ["alloc"] }
 //! ```
 //!
+//! The following features are not enabled by default:
+//!
+//! * `core-error`: This implements `core::error::Error` for `Exun` and
+//!   `UnexpectedError` without needing `std`. Combined with `alloc`, it also
+//!   allows `RawUnexpected` to hold errors. This requires Rust 1.81.
+//!
+//! * `single-thread`: This automatically enables `alloc`. It provides
+//!   `RawUnexpectedLocal`, which can hold errors that aren't `Send` or `Sync`.
+//!
+//! * `nom`: This allows an `Exun` to be converted into a `nom::Err`, for use in
+//!   parsers built with `nom`.
+//!
+//! * `log`: This adds `Exun::or_log_default`, which logs unexpected errors using
+//!   the `log` crate.
+//!
+//! * `serde`: This implements `Serialize` and `Deserialize` for `Exun`.
+//!   `RawUnexpected` and `UnexpectedError` aren't supported.
+//!
+//! * `backtrace`: This automatically enables `std`. It captures a backtrace
+//!   whenever a `RawUnexpected` is created, which can be retrieved with
+//!   `RawUnexpected::backtrace`. This requires Rust 1.65.
+//!
+//! * `proptest`: This implements `proptest::arbitrary::Arbitrary` for `Exun`,
+//!   for use in property-based tests.
+//!
+//! * `quickcheck`: This implements `quickcheck::Arbitrary` for `Exun`, for use
+//!   in property-based tests.
+//!
 //! ## Examples
 //!
 //! ```
@@ -84,7 +112,7 @@
 //!
 //! fn first(list: &[i32]) -> Result<i32, RawUnexpected> {
 //!     // for options, the `unexpect_none` method can be used
-//!     let num = list.get(0).unexpect_none()?;
+//!     let num = *list.get(0).unexpect_none()?;
 //!     Ok(num)
 //! }
 //! ```
@@ -153,24 +181,43 @@
 //! [`Result::unexpect_msg`]: `ResultMsgExt::unexpect_msg`
 //!
 
-#[cfg(all(feature = "alloc", not(feature = "std")))]
+#[cfg(all(any(feature = "alloc", feature = "quickcheck"), not(feature = "std")))]
 extern crate alloc;
 
+mod classify;
 mod exun;
+mod macros;
 mod result;
 mod unexpected;
 
 #[cfg(feature = "std")]
-pub use result::ResultErrorExt;
+pub use result::{ResultContextExt, ResultErrorExt};
 
+#[cfg(feature = "alloc")]
+pub use crate::exun::{collect_until_unexpected, dedup_unexpected, scan_expected, PartialExun};
 #[cfg(feature = "alloc")]
 pub use result::ResultMsgExt;
 
-pub use crate::exun::Exun;
+pub use crate::exun::{count_outcomes, guard, Exun, IntoIter, Iter, IterMut};
+pub use classify::Classify;
 pub use result::{ResultExunExt, ResultNoneExt};
 pub use unexpected::{RawUnexpected, UnexpectedError};
+
+#[cfg(any(feature = "std", feature = "core-error"))]
+pub use unexpected::Chain;
+#[cfg(feature = "single-thread")]
+pub use unexpected::RawUnexpectedLocal;
 pub use Exun::{Expected, Unexpected};
 
+#[doc(hidden)]
+#[cfg(feature = "alloc")]
+pub mod __private {
+	#[cfg(not(feature = "std"))]
+	pub use alloc::format;
+	#[cfg(feature = "std")]
+	pub use std::format;
+}
+
 /// A type alias for [`Exun<E, RawUnexpected>`]
 #[cfg(feature = "alloc")]
 pub type Expect<E> = Exun<E, RawUnexpected>;
@@ -0,0 +1,230 @@
+/// Creates a [`RawUnexpected`].
+///
+/// Given a string literal, or a format string followed by its arguments, the
+/// error is created using [`RawUnexpected::msg`]. Otherwise, the argument
+/// should be a value which implements [`Error`], and is passed to
+/// [`RawUnexpected::new`]. To use a value which doesn't implement [`Error`],
+/// call [`RawUnexpected::msg`] directly.
+///
+/// # Examples
+///
+/// ```
+/// use exun::*;
+///
+/// let x = unexpected!("failed");
+/// assert_eq!(x.to_string(), "failed");
+///
+/// let port = 80000;
+/// let x = unexpected!("port {} is too large", port);
+/// assert_eq!(x.to_string(), "port 80000 is too large");
+/// ```
+///
+/// ```
+/// use exun::*;
+///
+/// # #[cfg(feature = "std")]
+/// # fn main() {
+/// let x = unexpected!(core::fmt::Error);
+/// assert_eq!(x.to_string(), core::fmt::Error.to_string());
+/// assert!(x.is::<core::fmt::Error>());
+/// # }
+/// # #[cfg(not(feature = "std"))]
+/// # fn main() {}
+/// ```
+///
+/// [`Error`]: std::error::Error
+/// [`RawUnexpected`]: crate::RawUnexpected
+/// [`RawUnexpected::msg`]: crate::RawUnexpected::msg
+/// [`RawUnexpected::new`]: crate::RawUnexpected::new
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! unexpected {
+	($msg:literal $(,)?) => {
+		$crate::RawUnexpected::msg($crate::__private::format!($msg))
+	};
+	($err:expr $(,)?) => {
+		$crate::RawUnexpected::new($err)
+	};
+	($fmt:expr, $($arg:tt)*) => {
+		$crate::RawUnexpected::msg($crate::__private::format!($fmt, $($arg)*))
+	};
+}
+
+/// Returns early with an unexpected error.
+///
+/// This is equivalent to `return Err(unexpected!(...).into())`. See
+/// [`unexpected!`] for the arguments it accepts. The error is converted with
+/// [`Into`], so the function may return anything that a [`RawUnexpected`] can
+/// be converted into, such as [`UnexpectedError`].
+///
+/// # Examples
+///
+/// ```
+/// use exun::*;
+///
+/// fn check_port(port: u32) -> Result<u16, RawUnexpected> {
+///     if port > 65535 {
+///         bail_unexpected!("port {} is too large", port);
+///     }
+///
+///     Ok(port as u16)
+/// }
+///
+/// assert_eq!(check_port(80).unwrap(), 80);
+/// assert_eq!(check_port(80000).unwrap_err().to_string(), "port 80000 is too large");
+///
+/// fn fail() -> Result<(), UnexpectedError> {
+///     bail_unexpected!("this always fails");
+/// }
+///
+/// assert_eq!(fail().unwrap_err().to_string(), "this always fails");
+/// ```
+///
+/// ```
+/// use exun::*;
+///
+/// # #[cfg(feature = "std")]
+/// # fn main() {
+/// fn write() -> Result<(), RawUnexpected> {
+///     bail_unexpected!(core::fmt::Error);
+/// }
+///
+/// assert!(write().unwrap_err().is::<core::fmt::Error>());
+/// # }
+/// # #[cfg(not(feature = "std"))]
+/// # fn main() {}
+/// ```
+///
+/// [`RawUnexpected`]: crate::RawUnexpected
+/// [`UnexpectedError`]: crate::UnexpectedError
+/// [`unexpected!`]: crate::unexpected
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! bail_unexpected {
+	($($arg:tt)*) => {
+		return ::core::result::Result::Err($crate::unexpected!($($arg)*).into())
+	};
+}
+
+/// Returns early with an unexpected error if a condition isn't true.
+///
+/// This is equivalent to `if !cond { bail_unexpected!(...); }`. The message
+/// may be a string literal, or a format string followed by its arguments.
+/// See [`bail_unexpected!`] for details.
+///
+/// # Examples
+///
+/// ```
+/// use exun::*;
+///
+/// fn check_port(port: u32) -> Result<u16, RawUnexpected> {
+///     ensure_unexpected!(port != 0, "port must not be zero");
+///     ensure_unexpected!(port <= 65535, "port {} is too large", port);
+///     Ok(port as u16)
+/// }
+///
+/// assert_eq!(check_port(80).unwrap(), 80);
+/// assert_eq!(check_port(0).unwrap_err().to_string(), "port must not be zero");
+/// assert_eq!(check_port(80000).unwrap_err().to_string(), "port 80000 is too large");
+///
+/// fn check_len(list: &[u8]) -> Result<usize, UnexpectedError> {
+///     ensure_unexpected!(!list.is_empty(), "list is empty");
+///     Ok(list.len())
+/// }
+///
+/// assert_eq!(check_len(&[1, 2]).unwrap(), 2);
+/// assert_eq!(check_len(&[]).unwrap_err().to_string(), "list is empty");
+/// ```
+///
+/// [`bail_unexpected!`]: crate::bail_unexpected
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! ensure_unexpected {
+	($cond:expr, $msg:literal $(,)?) => {
+		if !$cond {
+			$crate::bail_unexpected!($msg);
+		}
+	};
+	($cond:expr, $fmt:expr, $($arg:tt)*) => {
+		if !$cond {
+			$crate::bail_unexpected!($fmt, $($arg)*);
+		}
+	};
+}
+
+/// Implements [`Classify`] for an enum whose variants are each either
+/// expected or unexpected.
+///
+/// List the patterns for the expected variants after `expected:`, and the
+/// patterns for the unexpected variants after `unexpected:`, separated by
+/// `|`. The enum is then classified as itself, so `classify` returns an
+/// `Exun<T, T>`. Every variant must be listed on one side, so adding a new
+/// variant to the enum won't compile until it's been classified.
+///
+/// # Examples
+///
+/// ```
+/// use exun::*;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum DecodeError {
+///     UnsupportedFormat(String),
+///     Truncated,
+///     Corrupt(&'static str),
+/// }
+///
+/// impl_classify! {
+///     DecodeError {
+///         // users can give us any file, so this is expected
+///         expected: DecodeError::UnsupportedFormat(_),
+///         // but we never expect a file to be damaged
+///         unexpected: DecodeError::Truncated | DecodeError::Corrupt(_),
+///     }
+/// }
+///
+/// let error = DecodeError::UnsupportedFormat("webp".to_string());
+/// assert_eq!(error.classify(), Expected(DecodeError::UnsupportedFormat("webp".to_string())));
+///
+/// let error = DecodeError::Truncated;
+/// assert_eq!(error.classify(), Unexpected(DecodeError::Truncated));
+///
+/// let error = DecodeError::Corrupt("bad header");
+/// assert_eq!(error.classify(), Unexpected(DecodeError::Corrupt("bad header")));
+/// ```
+///
+/// Forgetting a variant is an error:
+///
+/// ```compile_fail
+/// use exun::*;
+///
+/// enum DecodeError {
+///     UnsupportedFormat(String),
+///     Truncated,
+///     Corrupt(&'static str),
+/// }
+///
+/// impl_classify! {
+///     DecodeError {
+///         expected: DecodeError::UnsupportedFormat(_),
+///         unexpected: DecodeError::Truncated,
+///     }
+/// }
+/// ```
+///
+/// [`Classify`]: crate::Classify
+#[macro_export]
+macro_rules! impl_classify {
+	($ty:ty {
+		expected: $($expected:pat)|+,
+		unexpected: $($unexpected:pat)|+ $(,)?
+	}) => {
+		impl $crate::Classify<$ty, $ty> for $ty {
+			fn classify(self) -> $crate::Exun<$ty, $ty> {
+				match &self {
+					$($expected)|+ => $crate::Expected(self),
+					$($unexpected)|+ => $crate::Unexpected(self),
+				}
+			}
+		}
+	};
+}
@@ -1,4 +1,6 @@
 use core::fmt::Debug;
+#[cfg(feature = "std")]
+use core::fmt::Display;
 
 #[cfg(feature = "std")]
 use std::error::Error;
@@ -13,11 +15,18 @@ mod sealed {
 
 use sealed::Sealed;
 
-/// Provides [`Result::unexpect`]
+/// Provides [`Result::unexpect`] and [`Result::classify`]
 ///
 /// [`Result::unexpect`]: `ResultErrorExt::unexpect`
+/// [`Result::classify`]: `ResultErrorExt::classify`
 #[cfg(feature = "std")]
 pub trait ResultErrorExt<T>: Sealed {
+	/// The error which is passed to the classifier in
+	/// [`classify`](ResultErrorExt::classify).
+	///
+	/// This is `E` for [`Result<T, E>`], and `()` for [`Option<T>`].
+	type Source;
+
 	/// Converts [`Result<T, E>`] to [`Result<T, RawUnexpected>`].
 	///
 	/// # Examples
@@ -57,35 +66,222 @@ pub trait ResultErrorExt<T>: Sealed {
 	/// [`Exun`]: `crate::Exun`
 	#[allow(clippy::missing_errors_doc)]
 	fn unexpect(self) -> Result<T, RawUnexpected>;
+
+	/// Converts [`Result<T, E>`] to [`Result<T, Exun<F, RawUnexpected>>`] by
+	/// passing the error to a classifier.
+	///
+	/// The classifier decides whether the error is [`Expected`]. Anything it
+	/// leaves [`Unexpected`] is converted into a [`RawUnexpected`]. For an
+	/// [`Option`], the classifier is called with `()` if the value is
+	/// [`None`], and an unexpected `None` becomes [`RawUnexpected::none`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::error::Error;
+	/// use std::fmt::{self, Display};
+	///
+	/// use exun::*;
+	///
+	/// #[derive(Debug, PartialEq)]
+	/// enum DecodeError {
+	///     UnsupportedFormat(String),
+	///     Truncated,
+	/// }
+	///
+	/// impl Display for DecodeError {
+	///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+	///         match self {
+	///             DecodeError::UnsupportedFormat(format) => write!(f, "unsupported format: {}", format),
+	///             DecodeError::Truncated => write!(f, "the image is truncated"),
+	///         }
+	///     }
+	/// }
+	///
+	/// impl Error for DecodeError {}
+	///
+	/// fn decode(bytes: &[u8]) -> Result<Vec<u8>, DecodeError> {
+	///     match bytes {
+	///         [b'P', b'6', ..] => Ok(bytes[2..].to_vec()),
+	///         [] => Err(DecodeError::Truncated),
+	///         _ => Err(DecodeError::UnsupportedFormat("webp".to_string())),
+	///     }
+	/// }
+	///
+	/// fn load_image(bytes: &[u8]) -> Result<Vec<u8>, Exun<String, RawUnexpected>> {
+	///     decode(bytes).classify(|error| match error {
+	///         // users can give us any file, so this is expected
+	///         DecodeError::UnsupportedFormat(format) => Expected(format),
+	///         // but we never expect a file to be damaged
+	///         error => Unexpected(error),
+	///     })
+	/// }
+	///
+	/// assert_eq!(load_image(b"P6rgb").unwrap(), b"rgb");
+	/// assert_eq!(load_image(b"RIFF").unwrap_err().unwrap(), "webp");
+	///
+	/// let error = load_image(b"").unwrap_err().unwrap_unexpected();
+	/// assert_eq!(error.downcast_ref(), Some(&DecodeError::Truncated));
+	/// ```
+	///
+	/// Use with [`Option`]
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let option: Option<i32> = None;
+	/// let res: Result<i32, Exun<&str, RawUnexpected>> = option.classify(|()| Expected("missing"));
+	/// assert_eq!(res.unwrap_err().unwrap(), "missing");
+	///
+	/// let option: Option<i32> = None;
+	/// let res: Result<i32, Exun<&str, RawUnexpected>> = option.classify(Unexpected);
+	/// assert!(res.unwrap_err().unwrap_unexpected().is_none());
+	/// ```
+	///
+	/// [`Result<T, Exun<F, RawUnexpected>>`]: `crate::Exun`
+	/// [`Expected`]: `crate::Expected`
+	/// [`Unexpected`]: `crate::Unexpected`
+	#[allow(clippy::missing_errors_doc)]
+	fn classify<F>(
+		self,
+		f: impl FnOnce(Self::Source) -> Exun<F, Self::Source>,
+	) -> Result<T, Exun<F, RawUnexpected>>;
 }
 
 #[cfg(feature = "std")]
 impl<T, E: Error + Send + Sync + 'static> ResultErrorExt<T> for Result<T, E> {
+	type Source = E;
+
 	fn unexpect(self) -> Result<T, RawUnexpected> {
 		self.map_err(RawUnexpected::new)
 	}
+
+	fn classify<F>(self, f: impl FnOnce(E) -> Exun<F, E>) -> Result<T, Exun<F, RawUnexpected>> {
+		self.map_err(|e| f(e).map_unexpected(RawUnexpected::new))
+	}
 }
 
 #[cfg(feature = "std")]
 impl<T> ResultErrorExt<T> for Result<T, RawUnexpected> {
+	type Source = RawUnexpected;
+
 	fn unexpect(self) -> Self {
 		self
 	}
+
+	fn classify<F>(
+		self,
+		f: impl FnOnce(RawUnexpected) -> Exun<F, RawUnexpected>,
+	) -> Result<T, Exun<F, RawUnexpected>> {
+		self.map_err(f)
+	}
 }
 
 #[cfg(feature = "std")]
 impl<T> ResultErrorExt<T> for Option<T> {
+	type Source = ();
+
 	fn unexpect(self) -> Result<T, RawUnexpected> {
 		self.ok_or_else(RawUnexpected::none)
 	}
+
+	fn classify<F>(self, f: impl FnOnce(()) -> Exun<F, ()>) -> Result<T, Exun<F, RawUnexpected>> {
+		self.ok_or_else(|| f(()).map_unexpected(|()| RawUnexpected::none()))
+	}
+}
+
+/// Provides [`Result::context`] and [`Result::with_context`]
+///
+/// [`Result::context`]: `ResultContextExt::context`
+/// [`Result::with_context`]: `ResultContextExt::with_context`
+#[cfg(feature = "std")]
+pub trait ResultContextExt<T>: Sealed {
+	/// Converts [`Result<T, E>`] to [`Result<T, RawUnexpected>`], wrapping the
+	/// error with a message describing what was happening when it occurred.
+	///
+	/// See [`RawUnexpected::context`] for details.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::error::Error;
+	///
+	/// use exun::*;
+	///
+	/// fn parse_port(port: &str) -> Result<u16, RawUnexpected> {
+	///     port.parse::<u16>().context("invalid port")
+	/// }
+	///
+	/// let error = parse_port("http").unwrap_err();
+	/// assert_eq!(error.to_string(), "invalid port");
+	/// assert_eq!(error.source().unwrap().to_string(), "invalid digit found in string");
+	///
+	/// let error = Err::<(), _>(error).context("failed to load config").unwrap_err();
+	/// assert_eq!(error.to_string(), "failed to load config");
+	/// let source = error.source().unwrap();
+	/// assert_eq!(source.to_string(), "invalid port");
+	/// assert_eq!(source.source().unwrap().to_string(), "invalid digit found in string");
+	/// ```
+	fn context<C: Display + Send + Sync + 'static>(self, ctx: C) -> Result<T, RawUnexpected>;
+
+	/// Converts [`Result<T, E>`] to [`Result<T, RawUnexpected>`], wrapping the
+	/// error with a message created by `f`.
+	///
+	/// Unlike [`context`], `f` is only called if there is an error.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// fn parse_port(port: &str) -> Result<u16, RawUnexpected> {
+	///     port.parse::<u16>().with_context(|| format!("invalid port: {}", port))
+	/// }
+	///
+	/// assert_eq!(parse_port("80").unwrap(), 80);
+	/// assert_eq!(parse_port("http").unwrap_err().to_string(), "invalid port: http");
+	/// ```
+	///
+	/// [`context`]: `ResultContextExt::context`
+	fn with_context<C: Display + Send + Sync + 'static, F: FnOnce() -> C>(
+		self,
+		f: F,
+	) -> Result<T, RawUnexpected>;
+}
+
+#[cfg(feature = "std")]
+impl<T, E: Error + Send + Sync + 'static> ResultContextExt<T> for Result<T, E> {
+	fn context<C: Display + Send + Sync + 'static>(self, ctx: C) -> Result<T, RawUnexpected> {
+		self.map_err(|e| RawUnexpected::new(e).context(ctx))
+	}
+
+	fn with_context<C: Display + Send + Sync + 'static, F: FnOnce() -> C>(
+		self,
+		f: F,
+	) -> Result<T, RawUnexpected> {
+		self.map_err(|e| RawUnexpected::new(e).context(f()))
+	}
+}
+
+#[cfg(feature = "std")]
+impl<T> ResultContextExt<T> for Result<T, RawUnexpected> {
+	fn context<C: Display + Send + Sync + 'static>(self, ctx: C) -> Self {
+		self.map_err(|e| e.context(ctx))
+	}
+
+	fn with_context<C: Display + Send + Sync + 'static, F: FnOnce() -> C>(self, f: F) -> Self {
+		self.map_err(|e| e.context(f()))
+	}
 }
 
-/// Provides [`Result::unexpect_msg`]
+/// Provides [`Result::unexpect_msg`] and [`Option::unexpect_msg`]
 ///
 /// [`Result::unexpect_msg`]: `ResultMsgExt::unexpect_msg`
+/// [`Option::unexpect_msg`]: `ResultMsgExt::unexpect_msg`
 #[cfg(feature = "alloc")]
 pub trait ResultMsgExt<T>: Sealed {
-	/// Converts [`Result<T, E>`] to [`Result<T, RawUnexpected>`].
+	/// Converts [`Result<T, E>`] or [`Option<T>`] to
+	/// [`Result<T, RawUnexpected>`].
 	///
 	/// This is provided for compatibility with `no_std`. If your type
 	/// implements [`Error`], then you should prefer that instead.
@@ -121,6 +317,16 @@ pub trait ResultMsgExt<T>: Sealed {
 	/// }
 	/// ```
 	///
+	/// Use with [`Option`]
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let option: Option<i32> = None;
+	/// let res: Result<i32, RawUnexpected> = option.unexpect_msg();
+	/// assert!(res.unwrap_err().is_none());
+	/// ```
+	///
 	/// [`Exun`]: `crate::Exun`
 	#[allow(clippy::missing_errors_doc)]
 	fn unexpect_msg(self) -> Result<T, RawUnexpected>;
@@ -133,6 +339,13 @@ impl<T, E: Errorable + 'static> ResultMsgExt<T> for Result<T, E> {
 	}
 }
 
+#[cfg(feature = "alloc")]
+impl<T> ResultMsgExt<T> for Option<T> {
+	fn unexpect_msg(self) -> Result<T, RawUnexpected> {
+		self.ok_or_else(RawUnexpected::none)
+	}
+}
+
 /// Provides [`Result::unexpect_none`] and [`Option::unexpect_none`]
 ///
 /// [`Result::unexpect_none`]: `ResultNoneExt::unexpect_none`
@@ -203,6 +416,44 @@ impl<T> ResultNoneExt<T> for Option<T> {
 	}
 }
 
+/// Provides methods for working with a [`Result<T, Exun<E, U>>`].
+///
+/// Methods which don't need to know about the [`Exun`], such as
+/// [`Result::unwrap_or_default`], are already provided by [`Result`]. For
+/// example, `unwrap_or_default` returns the default value for either kind of
+/// error:
+///
+/// ```
+/// use exun::*;
+///
+/// let x: Result<u32, Exun<&str, &str>> = Ok(2);
+/// assert_eq!(x.unwrap_or_default(), 2);
+///
+/// let x: Result<u32, Exun<&str, &str>> = Err(Expected("expected"));
+/// assert_eq!(x.unwrap_or_default(), 0);
+///
+/// let x: Result<u32, Exun<&str, &str>> = Err(Unexpected("unexpected"));
+/// assert_eq!(x.unwrap_or_default(), 0);
+/// ```
+///
+/// Similarly, [`Result::transpose`] turns a `Result<Option<T>, Exun<E, U>>`
+/// into an `Option<Result<T, Exun<E, U>>>`, keeping the [`Exun`] intact:
+///
+/// ```
+/// use exun::*;
+///
+/// let x: Result<Option<u32>, Exun<&str, &str>> = Ok(None);
+/// assert_eq!(x.transpose(), None);
+///
+/// let x: Result<Option<u32>, Exun<&str, &str>> = Ok(Some(2));
+/// assert_eq!(x.transpose(), Some(Ok(2)));
+///
+/// let x: Result<Option<u32>, Exun<&str, &str>> = Err(Expected("expected"));
+/// assert_eq!(x.transpose(), Some(Err(Expected("expected"))));
+///
+/// let x: Result<Option<u32>, Exun<&str, &str>> = Err(Unexpected("unexpected"));
+/// assert_eq!(x.transpose(), Some(Err(Unexpected("unexpected"))));
+/// ```
 pub trait ResultExunExt<T, E, U>: Sealed {
 	/// Converts [`Result<T, Exun<E, U>>`] to [`Option<E>`].
 	///
@@ -240,6 +491,27 @@ pub trait ResultExunExt<T, E, U>: Sealed {
 	/// ```
 	fn unexpected_err(self) -> Option<U>;
 
+	/// Splits [`Result<T, Exun<E, U>>`] into its three possible outcomes.
+	///
+	/// Exactly one element of the returned tuple is [`Some`]: the success
+	/// value, the expected error, or the unexpected error.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::{Expected, Exun, ResultExunExt, Unexpected};
+	///
+	/// let x: Result<u32, Exun<&str, &str>> = Ok(2);
+	/// assert_eq!(x.split(), (Some(2), None, None));
+	///
+	/// let x: Result<u32, Exun<&str, &str>> = Err(Expected("expected"));
+	/// assert_eq!(x.split(), (None, Some("expected"), None));
+	///
+	/// let x: Result<u32, Exun<&str, &str>> = Err(Unexpected("unexpected"));
+	/// assert_eq!(x.split(), (None, None, Some("unexpected")));
+	/// ```
+	fn split(self) -> (Option<T>, Option<E>, Option<U>);
+
 	/// Maps a [`Result<T, Exun<E, U>>`] to `Result<T, Exun<F, U>>` by applying
 	/// a function to a contained `Err(Expected)` value, leaving the `Ok` and
 	/// `Err(Unexpected)` values untouched.
@@ -284,6 +556,107 @@ pub trait ResultExunExt<T, E, U>: Sealed {
 	/// ```
 	fn map_unexpected_err<F>(self, op: impl FnOnce(U) -> F) -> Result<T, Exun<E, F>>;
 
+	/// Converts a [`Result<T, Exun<E, U>>`] to `Result<T, Exun<F, U>>` by
+	/// converting a contained `Err(Expected)` value using [`From`].
+	///
+	/// This makes it easy to use the try operator to propagate an expected
+	/// error into a function with a wider expected error type. A blanket
+	/// `From<Exun<E, U>>` implementation isn't possible, because it would
+	/// conflict with `From<T> for T`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// # #[cfg(feature = "alloc")]
+	/// # fn main() {
+	/// #[derive(Debug, PartialEq)]
+	/// struct NotFound;
+	///
+	/// #[derive(Debug, PartialEq)]
+	/// enum RequestError {
+	///     NotFound(NotFound),
+	/// }
+	///
+	/// impl From<NotFound> for RequestError {
+	///     fn from(e: NotFound) -> Self {
+	///         Self::NotFound(e)
+	///     }
+	/// }
+	///
+	/// #[derive(Debug, PartialEq)]
+	/// enum AppError {
+	///     Request(RequestError),
+	/// }
+	///
+	/// impl From<RequestError> for AppError {
+	///     fn from(e: RequestError) -> Self {
+	///         Self::Request(e)
+	///     }
+	/// }
+	///
+	/// fn lookup(key: &str) -> Result<u32, Expect<NotFound>> {
+	///     match key {
+	///         "one" => Ok(1),
+	///         "" => Err(Unexpected(RawUnexpected::msg("empty key"))),
+	///         _ => Err(Expected(NotFound)),
+	///     }
+	/// }
+	///
+	/// fn handle(key: &str) -> Result<u32, Expect<RequestError>> {
+	///     Ok(lookup(key).expected_err_into()? + 1)
+	/// }
+	///
+	/// assert_eq!(handle("one").unwrap(), 2);
+	/// assert_eq!(
+	///     handle("two").unwrap_err().unwrap(),
+	///     RequestError::NotFound(NotFound)
+	/// );
+	/// assert!(handle("").unwrap_err().unexpected().is_some());
+	///
+	/// fn serve(key: &str) -> Result<u32, Expect<AppError>> {
+	///     Ok(handle(key).expected_err_into()? * 10)
+	/// }
+	///
+	/// assert_eq!(serve("one").unwrap(), 20);
+	/// assert_eq!(
+	///     serve("two").unwrap_err().unwrap(),
+	///     AppError::Request(RequestError::NotFound(NotFound))
+	/// );
+	/// assert_eq!(
+	///     serve("").unwrap_err().unwrap_unexpected().to_string(),
+	///     "empty key"
+	/// );
+	/// # }
+	/// # #[cfg(not(feature = "alloc"))]
+	/// # fn main() {}
+	/// ```
+	fn expected_err_into<F: From<E>>(self) -> Result<T, Exun<F, U>>;
+
+	/// Converts a [`Result<T, Exun<E, U>>`] to `Result<T, U>` by applying a
+	/// function to a contained `Err(Expected)` value, turning it into an `Ok`
+	/// value. The `Ok` and `Err(Unexpected)` values are left untouched.
+	///
+	/// This is useful when an expected error is really just another valid
+	/// outcome, which can be recovered from.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x: Result<u32, Exun<&str, &str>> = Ok(2);
+	/// assert_eq!(x.recover_expected(|e| e.len() as u32), Ok(2));
+	///
+	/// let x: Result<u32, Exun<&str, &str>> = Err(Expected("foo"));
+	/// assert_eq!(x.recover_expected(|e| e.len() as u32), Ok(3));
+	///
+	/// let x: Result<u32, Exun<&str, &str>> = Err(Unexpected("bar"));
+	/// assert_eq!(x.recover_expected(|e| e.len() as u32), Err("bar"));
+	/// ```
+	fn recover_expected(self, f: impl FnOnce(E) -> T) -> Result<T, U>;
+
 	/// Converts [`Result<T, Exun<E, U>>`] to `Result<T, E>`, consuming the
 	/// self value.
 	///
@@ -363,6 +736,65 @@ pub trait ResultExunExt<T, E, U>: Sealed {
 	where
 		T: Debug,
 		E: Debug;
+
+	/// Converts [`Result<T, Exun<E, U>>`] to
+	/// `Result<T, Box<dyn Error + Send + Sync>>`, boxing whichever error is
+	/// present.
+	///
+	/// This discards the distinction between the expected and unexpected
+	/// errors, which is useful when handing the result off to code that
+	/// works with any error.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::{Expected, Exun, ResultExunExt, Unexpected};
+	/// use core::fmt::Error;
+	/// use std::num::ParseIntError;
+	///
+	/// let parse_error = "a".parse::<u32>().unwrap_err();
+	///
+	/// let x: Result<u32, Exun<Error, ParseIntError>> = Err(Expected(Error));
+	/// let error = x.boxed_err().unwrap_err();
+	/// assert!(error.downcast_ref::<Error>().is_some());
+	///
+	/// let x: Result<u32, Exun<Error, ParseIntError>> = Err(Unexpected(parse_error.clone()));
+	/// let error = x.boxed_err().unwrap_err();
+	/// assert_eq!(error.downcast_ref::<ParseIntError>(), Some(&parse_error));
+	/// ```
+	#[cfg(feature = "std")]
+	fn boxed_err(self) -> Result<T, Box<dyn Error + Send + Sync>>
+	where
+		E: Error + Send + Sync + 'static,
+		U: Error + Send + Sync + 'static;
+
+	/// Converts [`Result<T, Exun<E, U>>`] to [`Result<T, RawUnexpected>`],
+	/// treating the expected error as unexpected too.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	/// use std::num::ParseIntError;
+	///
+	/// let x: Result<u32, Exun<ParseIntError, RawUnexpected>> =
+	///     Err(Expected("a".parse::<u32>().unwrap_err()));
+	/// let error = x.unexpect_all().unwrap_err();
+	/// assert_eq!(error.to_string(), "invalid digit found in string");
+	/// assert!(error.is::<ParseIntError>());
+	///
+	/// let x: Result<u32, Exun<ParseIntError, RawUnexpected>> =
+	///     Err(Unexpected(RawUnexpected::msg("failure")));
+	/// assert_eq!(x.unexpect_all().unwrap_err().to_string(), "failure");
+	///
+	/// let x: Result<u32, Exun<ParseIntError, RawUnexpected>> = Ok(2);
+	/// assert_eq!(x.unexpect_all().unwrap(), 2);
+	/// ```
+	#[cfg(feature = "std")]
+	fn unexpect_all(self) -> Result<T, RawUnexpected>
+	where
+		E: Error + Send + Sync + 'static,
+		U: Into<RawUnexpected>;
 }
 
 impl<T, E, U> ResultExunExt<T, E, U> for Result<T, Exun<E, U>> {
@@ -374,6 +806,14 @@ impl<T, E, U> ResultExunExt<T, E, U> for Result<T, Exun<E, U>> {
 		self.err()?.unexpected()
 	}
 
+	fn split(self) -> (Option<T>, Option<E>, Option<U>) {
+		match self {
+			Ok(value) => (Some(value), None, None),
+			Err(Exun::Expected(e)) => (None, Some(e), None),
+			Err(Exun::Unexpected(u)) => (None, None, Some(u)),
+		}
+	}
+
 	fn map_expected_err<F>(self, op: impl FnOnce(E) -> F) -> Result<T, Exun<F, U>> {
 		self.map_err(|e| e.map(op))
 	}
@@ -382,6 +822,19 @@ impl<T, E, U> ResultExunExt<T, E, U> for Result<T, Exun<E, U>> {
 		self.map_err(|e| e.map_unexpected(op))
 	}
 
+	fn expected_err_into<F: From<E>>(self) -> Result<T, Exun<F, U>> {
+		self.map_expected_err(F::from)
+	}
+
+	fn recover_expected(self, f: impl FnOnce(E) -> T) -> Result<T, U> {
+		match self {
+			Ok(value) => Ok(value),
+			Err(Exun::Expected(e)) => Ok(f(e)),
+			Err(Exun::Unexpected(u)) => Err(u),
+		}
+	}
+
+	#[track_caller]
 	fn unwrap_result(self) -> Result<T, E>
 	where
 		U: Debug,
@@ -392,6 +845,7 @@ impl<T, E, U> ResultExunExt<T, E, U> for Result<T, Exun<E, U>> {
 		}
 	}
 
+	#[track_caller]
 	fn unwrap_expected_err(self) -> E
 	where
 		T: Debug,
@@ -400,6 +854,7 @@ impl<T, E, U> ResultExunExt<T, E, U> for Result<T, Exun<E, U>> {
 		self.unwrap_err().unwrap()
 	}
 
+	#[track_caller]
 	fn unwrap_unexpected_err(self) -> U
 	where
 		T: Debug,
@@ -407,4 +862,28 @@ impl<T, E, U> ResultExunExt<T, E, U> for Result<T, Exun<E, U>> {
 	{
 		self.unwrap_err().unwrap_unexpected()
 	}
+
+	#[cfg(feature = "std")]
+	fn boxed_err(self) -> Result<T, Box<dyn Error + Send + Sync>>
+	where
+		E: Error + Send + Sync + 'static,
+		U: Error + Send + Sync + 'static,
+	{
+		self.map_err(|error| match error {
+			Exun::Expected(e) => Box::new(e) as Box<dyn Error + Send + Sync>,
+			Exun::Unexpected(u) => Box::new(u),
+		})
+	}
+
+	#[cfg(feature = "std")]
+	fn unexpect_all(self) -> Result<T, RawUnexpected>
+	where
+		E: Error + Send + Sync + 'static,
+		U: Into<RawUnexpected>,
+	{
+		self.map_err(|error| match error {
+			Exun::Expected(e) => RawUnexpected::new(e),
+			Exun::Unexpected(u) => u.into(),
+		})
+	}
 }
@@ -1,148 +1,1245 @@
 use core::fmt::{self, Debug, Display};
+#[cfg(any(feature = "std", feature = "core-error"))]
+use core::iter::FusedIterator;
 
 #[cfg(all(feature = "alloc", not(feature = "std")))]
 use alloc::boxed::Box;
+#[cfg(all(feature = "alloc", feature = "core-error", not(feature = "std")))]
+use alloc::string::FromUtf8Error;
 #[cfg(all(feature = "alloc", not(feature = "std")))]
-use alloc::string::String;
+use alloc::string::{String, ToString};
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+#[cfg(feature = "backtrace")]
+use std::backtrace::{Backtrace, BacktraceStatus};
+#[cfg(feature = "std")]
+use std::string::FromUtf8Error;
+
+#[cfg(all(feature = "core-error", not(feature = "std")))]
+use core::error::Error;
+#[cfg(feature = "std")]
+use std::error::Error;
+
+pub trait Errorable: Display + Debug + Send + Sync {}
+impl<T: Display + Debug + Send + Sync + ?Sized> Errorable for T {}
+
+#[cfg(feature = "single-thread")]
+pub trait LocalErrorable: Display + Debug {}
+#[cfg(feature = "single-thread")]
+impl<T: Display + Debug + ?Sized> LocalErrorable for T {}
+
+#[derive(Debug)]
+enum ErrorTy {
+	None,
+	#[cfg(feature = "alloc")]
+	Message(Box<dyn Errorable + 'static>),
+	#[cfg(any(feature = "std", all(feature = "alloc", feature = "core-error")))]
+	Error(Box<dyn Error + Send + Sync + 'static>),
+	// without an error trait, the wrapped error is only used by `Debug`
+	#[cfg(feature = "alloc")]
+	#[cfg_attr(not(any(feature = "std", feature = "core-error")), allow(dead_code))]
+	Context(String, Box<UnexpectedError>),
+}
+
+/// A wrapper for an error that isn't expected to occur.
+///
+/// This implements [`From<T>`] where `T` implements [`Error`], [`Send`],
+/// [`Sync`] and `'static` for easy conversion. Because of this, it cannot
+/// itself implement [`Error`]. If you need a type that implements [`Error`]
+/// but doesn't implement `From<Error>`, use [`UnexpectedError`].
+///
+/// For the same reason, it can't implement `From<&'static str>` or
+/// `From<String>`, since the standard library could implement [`Error`] for
+/// those types in the future. Use [`RawUnexpected::from_static`] or
+/// [`RawUnexpected::from_string`] instead.
+#[derive(Debug)]
+pub struct RawUnexpected {
+	internal: ErrorTy,
+	#[cfg(feature = "alloc")]
+	metadata: Vec<(&'static str, String)>,
+	// the backtrace feature requires Rust 1.65
+	#[cfg(feature = "backtrace")]
+	#[allow(clippy::incompatible_msrv)]
+	backtrace: Option<Backtrace>,
+}
+
+impl Display for RawUnexpected {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match &self.internal {
+			ErrorTy::None => Display::fmt("Called `unexpect` on a `None` value", f),
+			#[cfg(feature = "alloc")]
+			ErrorTy::Message(m) => Display::fmt(&m, f),
+			#[cfg(any(feature = "std", all(feature = "alloc", feature = "core-error")))]
+			ErrorTy::Error(e) => Display::fmt(&e, f),
+			#[cfg(feature = "alloc")]
+			ErrorTy::Context(c, _) => Display::fmt(c, f),
+		}
+	}
+}
+
+#[cfg(any(feature = "std", all(feature = "alloc", feature = "core-error")))]
+impl<T: Error + Send + Sync + 'static> From<T> for RawUnexpected {
+	fn from(e: T) -> Self {
+		Self::new(e)
+	}
+}
+
+impl RawUnexpected {
+	/// Create a new `RawUnexpected` from any [`Error`] type.
+	///
+	/// The error must be thread-safe and `'static` so that the
+	/// `RawUnexpected` will be too.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x = RawUnexpected::new(core::fmt::Error);
+	/// ```
+	#[cfg(any(feature = "std", all(feature = "alloc", feature = "core-error")))]
+	#[must_use]
+	pub fn new<E: Error + Send + Sync + 'static>(error: E) -> Self {
+		Self::from_internal(ErrorTy::Error(Box::new(error)))
+	}
+
+	/// Create a new `RawUnexpected` from an error which has already been
+	/// boxed.
+	///
+	/// The box is stored directly, without being boxed again, so the original
+	/// error can still be recovered with [`RawUnexpected::downcast`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::error::Error;
+	/// use std::num::ParseIntError;
+	///
+	/// use exun::*;
+	///
+	/// let parse_error = "a".parse::<u32>().unwrap_err();
+	/// let boxed: Box<dyn Error + Send + Sync> = Box::new(parse_error.clone());
+	/// let x = RawUnexpected::from_boxed(boxed);
+	/// assert_eq!(x.downcast_ref::<ParseIntError>(), Some(&parse_error));
+	/// assert_eq!(x.downcast::<ParseIntError>().unwrap(), parse_error);
+	/// ```
+	#[cfg(any(feature = "std", all(feature = "alloc", feature = "core-error")))]
+	#[must_use]
+	pub fn from_boxed(error: Box<dyn Error + Send + Sync + 'static>) -> Self {
+		Self::from_internal(ErrorTy::Error(error))
+	}
+
+	/// Create a new `RawUnexpected` from a printable error message.
+	///
+	/// If the argument implements [`Error`], prefer [`RawUnexpected::new`]
+	/// instead, which preserves the source.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x = RawUnexpected::msg("failed");
+	/// ```
+	#[cfg(feature = "alloc")]
+	#[must_use]
+	pub fn msg<E: Display + Debug + Send + Sync + 'static>(error: E) -> Self {
+		Self::from_internal(ErrorTy::Message(Box::new(error)))
+	}
+
+	/// Create a new `RawUnexpected` from any [`Error`] type.
+	///
+	/// This is an alias for [`RawUnexpected::new`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x = RawUnexpected::from_err(core::fmt::Error);
+	/// ```
+	#[cfg(any(feature = "std", all(feature = "alloc", feature = "core-error")))]
+	#[must_use]
+	#[inline]
+	pub fn from_err<E: Error + Send + Sync + 'static>(error: E) -> Self {
+		Self::new(error)
+	}
+
+	/// Create a new `RawUnexpected` from a printable error message.
+	///
+	/// This is an alias for [`RawUnexpected::msg`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x = RawUnexpected::from_msg("failed");
+	/// ```
+	#[cfg(feature = "alloc")]
+	#[must_use]
+	#[inline]
+	pub fn from_msg<E: Display + Debug + Send + Sync + 'static>(error: E) -> Self {
+		Self::msg(error)
+	}
+
+	/// Create a new `RawUnexpected` from a static string message.
+	///
+	/// This behaves like [`RawUnexpected::msg`], but isn't generic.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x = RawUnexpected::from_static("failed");
+	/// assert_eq!(x.to_string(), "failed");
+	/// assert!(x.is_msg());
+	/// ```
+	#[cfg(feature = "alloc")]
+	#[must_use]
+	pub fn from_static(message: &'static str) -> Self {
+		Self::from_internal(ErrorTy::Message(Box::new(message)))
+	}
+
+	/// Create a new `RawUnexpected` from an owned string message.
+	///
+	/// This behaves like [`RawUnexpected::msg`], but isn't generic.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x = RawUnexpected::from_string(format!("failed after {} attempts", 3));
+	/// assert_eq!(x.to_string(), "failed after 3 attempts");
+	/// assert!(x.is_msg());
+	/// ```
+	#[cfg(feature = "alloc")]
+	#[must_use]
+	pub fn from_string(message: String) -> Self {
+		Self::from_internal(ErrorTy::Message(Box::new(message)))
+	}
+
+	/// Create a new `RawUnexpected` that is simply empty.
+	///
+	/// This is used for converting an [`Option<T>`] to a
+	/// [`Result<T, RawUnexpected>`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x = RawUnexpected::none();
+	/// ```
+	#[must_use]
+	pub const fn none() -> Self {
+		Self {
+			internal: ErrorTy::None,
+			#[cfg(feature = "alloc")]
+			metadata: Vec::new(),
+			#[cfg(feature = "backtrace")]
+			backtrace: None,
+		}
+	}
+
+	/// Create a new `RawUnexpected`, capturing a backtrace if enabled.
+	#[cfg(feature = "alloc")]
+	#[allow(clippy::missing_const_for_fn)]
+	#[cfg_attr(feature = "backtrace", allow(clippy::incompatible_msrv))]
+	fn from_internal(internal: ErrorTy) -> Self {
+		Self {
+			internal,
+			metadata: Vec::new(),
+			#[cfg(feature = "backtrace")]
+			backtrace: Some(Backtrace::capture())
+				.filter(|backtrace| backtrace.status() == BacktraceStatus::Captured),
+		}
+	}
+
+	/// The original error, ignoring any contexts.
+	#[allow(clippy::missing_const_for_fn)]
+	fn original(&self) -> &ErrorTy {
+		match &self.internal {
+			#[cfg(feature = "alloc")]
+			ErrorTy::Context(_, inner) => inner.0.original(),
+			internal => internal,
+		}
+	}
+
+	/// Returns `true` if `self` was created using [`RawUnexpected::none`].
+	///
+	/// Contexts are ignored, so this looks at the original error.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// assert!(RawUnexpected::none().is_none());
+	/// # #[cfg(feature = "alloc")]
+	/// assert!(!RawUnexpected::msg("failed").is_none());
+	/// # #[cfg(feature = "alloc")]
+	/// assert!(RawUnexpected::none().context("failed").is_none());
+	/// # #[cfg(feature = "std")]
+	/// assert!(!RawUnexpected::new(core::fmt::Error).is_none());
+	/// ```
+	#[must_use]
+	pub fn is_none(&self) -> bool {
+		matches!(self.original(), ErrorTy::None)
+	}
+
+	/// Returns `true` if `self` was created using [`RawUnexpected::msg`].
+	///
+	/// Contexts are ignored, so this looks at the original error.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// assert!(RawUnexpected::msg("failed").is_msg());
+	/// assert!(!RawUnexpected::none().is_msg());
+	/// assert!(RawUnexpected::msg("failed").context("failed to save").is_msg());
+	/// # #[cfg(feature = "std")]
+	/// assert!(!RawUnexpected::new(core::fmt::Error).is_msg());
+	/// ```
+	#[cfg(feature = "alloc")]
+	#[must_use]
+	pub fn is_msg(&self) -> bool {
+		matches!(self.original(), ErrorTy::Message(_))
+	}
+
+	/// Returns `true` if the original error is an [`Error`], meaning that it
+	/// can be reached through [`RawUnexpected::chain`]. If the original error
+	/// has no source, then it's returned by [`RawUnexpected::root_cause`].
+	///
+	/// Contexts are ignored, so this looks at the original error.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// assert!(RawUnexpected::new(core::fmt::Error).is_error());
+	/// assert!(RawUnexpected::from(core::fmt::Error).is_error());
+	/// assert!(RawUnexpected::new(core::fmt::Error).context("failed").is_error());
+	///
+	/// let x = RawUnexpected::new(core::fmt::Error)
+	///     .context("failed to format")
+	///     .context("failed to save");
+	/// assert!(x.is_error());
+	/// assert!(x.root_cause().unwrap().is::<core::fmt::Error>());
+	///
+	/// assert!(!RawUnexpected::msg("failed").is_error());
+	/// assert!(!RawUnexpected::none().is_error());
+	/// ```
+	#[cfg(any(feature = "std", all(feature = "alloc", feature = "core-error")))]
+	#[must_use]
+	pub fn is_error(&self) -> bool {
+		matches!(self.original(), ErrorTy::Error(_))
+	}
+
+	/// Get the error which `self` wraps, which is the next error in the
+	/// chain.
+	///
+	/// Without any contexts, this is the original error, and [`None`] is
+	/// returned if `self` was created using [`RawUnexpected::msg`] or
+	/// [`RawUnexpected::none`].
+	///
+	/// If [`RawUnexpected::context`] was called, this is the error it
+	/// wrapped. That's the original error if it was an [`Error`] wrapped by a
+	/// single context. Otherwise, it's the next layer, as an
+	/// [`UnexpectedError`], so this returns [`Some`] even for a message with
+	/// a context. Use [`RawUnexpected::chain`] to look at every layer.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// # #[cfg(feature = "alloc")]
+	/// # fn main() {
+	/// let x = RawUnexpected::new(core::fmt::Error);
+	/// assert!(x.source().unwrap().is::<core::fmt::Error>());
+	///
+	/// let x = RawUnexpected::msg("failed");
+	/// assert!(x.source().is_none());
+	///
+	/// let x = RawUnexpected::new(core::fmt::Error).context("failed to format");
+	/// assert!(x.source().unwrap().is::<core::fmt::Error>());
+	///
+	/// let x = RawUnexpected::new(core::fmt::Error)
+	///     .context("failed to format")
+	///     .context("failed to save");
+	/// assert_eq!(x.source().unwrap().to_string(), "failed to format");
+	///
+	/// let x = RawUnexpected::msg("failed").context("failed to save");
+	/// assert_eq!(x.source().unwrap().to_string(), "failed");
+	/// # }
+	/// # #[cfg(not(feature = "alloc"))]
+	/// # fn main() {}
+	/// ```
+	#[must_use]
+	#[cfg(any(feature = "std", feature = "core-error"))]
+	pub fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match &self.internal {
+			ErrorTy::None => None,
+			#[cfg(feature = "alloc")]
+			ErrorTy::Message(_) => None,
+			#[cfg(any(feature = "std", all(feature = "alloc", feature = "core-error")))]
+			ErrorTy::Error(e) => Some(&**e),
+			#[cfg(feature = "alloc")]
+			ErrorTy::Context(_, inner) => match &inner.0.internal {
+				ErrorTy::Error(e) => Some(&**e),
+				_ => Some(&**inner),
+			},
+		}
+	}
+
+	/// The first error in the chain which isn't displayed by `self`.
+	#[cfg(any(feature = "std", feature = "core-error"))]
+	fn cause(&self) -> Option<&(dyn Error + 'static)> {
+		#[cfg(feature = "alloc")]
+		if let ErrorTy::Context(..) = self.internal {
+			return self.source();
+		}
+
+		self.source().and_then(Error::source)
+	}
+
+	/// Convert the `RawUnexpected` into the original error.
+	///
+	/// Unlike [`RawUnexpected::source`], this looks through every context.
+	/// The contexts are discarded, along with any metadata attached to
+	/// `self`. This will return [`None`] if the original error was created
+	/// using [`RawUnexpected::msg`] or [`RawUnexpected::none`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::num::ParseIntError;
+	///
+	/// use exun::*;
+	///
+	/// let parse_error = "a".parse::<u32>().unwrap_err();
+	/// let x = RawUnexpected::new(parse_error.clone());
+	/// let source = x.into_source().unwrap();
+	/// assert_eq!(source.downcast_ref::<ParseIntError>(), Some(&parse_error));
+	///
+	/// let x = RawUnexpected::new(parse_error.clone())
+	///     .context("failed to parse")
+	///     .context("failed to load");
+	/// let source = x.into_source().unwrap();
+	/// assert_eq!(source.downcast_ref::<ParseIntError>(), Some(&parse_error));
+	///
+	/// let x = RawUnexpected::msg("failed");
+	/// assert!(x.into_source().is_none());
+	/// ```
+	#[must_use]
+	#[cfg(any(feature = "std", all(feature = "alloc", feature = "core-error")))]
+	pub fn into_source(self) -> Option<Box<dyn Error + Send + Sync + 'static>> {
+		match self.internal {
+			ErrorTy::None | ErrorTy::Message(_) => None,
+			ErrorTy::Error(e) => Some(e),
+			ErrorTy::Context(_, inner) => inner.0.into_source(),
+		}
+	}
+
+	/// Search the chain of errors for an error of type `E`.
+	///
+	/// This searches each error yielded by [`RawUnexpected::chain`], which
+	/// looks through any contexts, returning the first error of type `E`.
+	/// This can be used to recover a specific cause which has been wrapped by
+	/// other errors. If `self` was created using [`RawUnexpected::msg`], this
+	/// will return [`None`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::error::Error;
+	/// use std::fmt::{self, Display};
+	/// use std::num::ParseIntError;
+	///
+	/// use exun::*;
+	///
+	/// #[derive(Debug)]
+	/// struct ConfigError(ParseIntError);
+	///
+	/// impl Display for ConfigError {
+	///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+	///         write!(f, "invalid config")
+	///     }
+	/// }
+	///
+	/// impl Error for ConfigError {
+	///     fn source(&self) -> Option<&(dyn Error + 'static)> {
+	///         Some(&self.0)
+	///     }
+	/// }
+	///
+	/// # #[cfg(feature = "alloc")]
+	/// # fn main() {
+	/// let parse_error = "a".parse::<u32>().unwrap_err();
+	/// let x = RawUnexpected::new(ConfigError(parse_error.clone()));
+	/// assert_eq!(x.find_source::<ParseIntError>(), Some(&parse_error));
+	/// assert!(x.find_source::<ConfigError>().is_some());
+	/// assert!(x.find_source::<core::fmt::Error>().is_none());
+	/// # }
+	/// # #[cfg(not(feature = "alloc"))]
+	/// # fn main() {}
+	/// ```
+	#[must_use]
+	#[cfg(any(feature = "std", feature = "core-error"))]
+	pub fn find_source<E: Error + 'static>(&self) -> Option<&E> {
+		self.chain().find_map(|error| error.downcast_ref())
+	}
+
+	/// Returns an iterator over the chain of errors.
+	///
+	/// The iterator doesn't include `self`. It starts at the error returned
+	/// by [`RawUnexpected::source`], and follows each
+	/// [`source`](Error::source). With contexts, this yields the contexts
+	/// from the outermost inward, skipping the outermost one, which `self`
+	/// displays. Then it yields the original error, followed by its sources,
+	/// down to the root cause. If `self` was created using
+	/// [`RawUnexpected::msg`] or [`RawUnexpected::none`], and has no
+	/// contexts, the iterator is empty.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::io;
+	///
+	/// use exun::*;
+	///
+	/// # #[cfg(feature = "std")]
+	/// # fn main() {
+	/// let x = RawUnexpected::new(io::Error::from(io::ErrorKind::NotFound))
+	///     .context("failed to read config.toml")
+	///     .context("failed to load config")
+	///     .context("failed to start");
+	///
+	/// let chain: Vec<String> = x.chain().map(|e| e.to_string()).collect();
+	/// assert_eq!(
+	///     chain,
+	///     [
+	///         "failed to load config".to_string(),
+	///         "failed to read config.toml".to_string(),
+	///         io::Error::from(io::ErrorKind::NotFound).to_string(),
+	///     ]
+	/// );
+	///
+	/// assert_eq!(RawUnexpected::msg("failed").chain().count(), 0);
+	/// # }
+	/// # #[cfg(not(feature = "std"))]
+	/// # fn main() {}
+	/// ```
+	#[must_use]
+	#[cfg(any(feature = "std", feature = "core-error"))]
+	pub fn chain(&self) -> Chain<'_> {
+		Chain {
+			next: self.source(),
+		}
+	}
+
+	/// Returns the deepest error in the chain of errors.
+	///
+	/// This is the last error returned by [`RawUnexpected::chain`]. If `self`
+	/// was created using [`RawUnexpected::msg`] or [`RawUnexpected::none`],
+	/// this will return [`None`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::io;
+	///
+	/// use exun::*;
+	///
+	/// # #[cfg(feature = "std")]
+	/// # fn main() {
+	/// let x = RawUnexpected::new(io::Error::from(io::ErrorKind::NotFound))
+	///     .context("failed to read config.toml")
+	///     .context("failed to load config");
+	///
+	/// let root = x.root_cause().unwrap();
+	/// assert!(root.is::<io::Error>());
+	/// assert_eq!(root.to_string(), io::Error::from(io::ErrorKind::NotFound).to_string());
+	///
+	/// let x = RawUnexpected::new(core::fmt::Error);
+	/// assert!(x.root_cause().unwrap().is::<core::fmt::Error>());
+	///
+	/// assert!(RawUnexpected::msg("failed").root_cause().is_none());
+	/// # }
+	/// # #[cfg(not(feature = "std"))]
+	/// # fn main() {}
+	/// ```
+	#[must_use]
+	#[cfg(any(feature = "std", feature = "core-error"))]
+	pub fn root_cause(&self) -> Option<&(dyn Error + 'static)> {
+		self.chain().last()
+	}
+
+	/// Returns `true` if the original error has type `E`.
+	///
+	/// This will return `false` if `self` was created using
+	/// [`RawUnexpected::msg`] or [`RawUnexpected::none`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::num::ParseIntError;
+	///
+	/// use exun::*;
+	///
+	/// let x = RawUnexpected::new(core::fmt::Error);
+	/// assert!(x.is::<core::fmt::Error>());
+	/// assert!(!x.is::<ParseIntError>());
+	///
+	/// let x = RawUnexpected::msg("failed");
+	/// assert!(!x.is::<core::fmt::Error>());
+	///
+	/// let x = RawUnexpected::none();
+	/// assert!(!x.is::<core::fmt::Error>());
+	/// ```
+	#[must_use]
+	#[cfg(any(feature = "std", all(feature = "alloc", feature = "core-error")))]
+	pub fn is<E: Error + 'static>(&self) -> bool {
+		self.downcast_ref::<E>().is_some()
+	}
+
+	/// Get a reference to the original error, if it has type `E`.
+	///
+	/// This will return [`None`] if the error has a different type, or if
+	/// `self` was created using [`RawUnexpected::msg`] or
+	/// [`RawUnexpected::none`]. Unlike [`RawUnexpected::find_source`], the
+	/// sources of the original error aren't searched.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::error::Error;
+	/// use std::fmt::{self, Display};
+	///
+	/// use exun::*;
+	///
+	/// #[derive(Debug, PartialEq)]
+	/// struct TimeoutError(u32);
+	///
+	/// impl Display for TimeoutError {
+	///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+	///         write!(f, "timed out after {} seconds", self.0)
+	///     }
+	/// }
+	///
+	/// impl Error for TimeoutError {}
+	///
+	/// let x = RawUnexpected::new(TimeoutError(30));
+	/// assert_eq!(x.downcast_ref::<TimeoutError>(), Some(&TimeoutError(30)));
+	/// assert!(x.downcast_ref::<core::fmt::Error>().is_none());
+	///
+	/// let x = RawUnexpected::msg("failed");
+	/// assert!(x.downcast_ref::<TimeoutError>().is_none());
+	/// ```
+	#[must_use]
+	#[cfg(any(feature = "std", all(feature = "alloc", feature = "core-error")))]
+	pub fn downcast_ref<E: Error + 'static>(&self) -> Option<&E> {
+		match &self.internal {
+			ErrorTy::None | ErrorTy::Message(_) => None,
+			ErrorTy::Error(e) => e.downcast_ref(),
+			ErrorTy::Context(_, inner) => inner.0.downcast_ref(),
+		}
+	}
+
+	/// Get a mutable reference to the original error, if it has type `E`.
+	///
+	/// This will return [`None`] if the error has a different type, or if
+	/// `self` was created using [`RawUnexpected::msg`] or
+	/// [`RawUnexpected::none`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::error::Error;
+	/// use std::fmt::{self, Display};
+	///
+	/// use exun::*;
+	///
+	/// #[derive(Debug, PartialEq)]
+	/// struct TimeoutError {
+	///     seconds: u32,
+	///     retries: u32,
+	/// }
+	///
+	/// impl Display for TimeoutError {
+	///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+	///         write!(f, "timed out after {} seconds", self.seconds)
+	///     }
+	/// }
+	///
+	/// impl Error for TimeoutError {}
+	///
+	/// let mut x = RawUnexpected::new(TimeoutError { seconds: 30, retries: 0 });
+	/// if let Some(e) = x.downcast_mut::<TimeoutError>() {
+	///     e.retries = 3;
+	/// }
+	/// assert_eq!(
+	///     x.downcast_ref::<TimeoutError>(),
+	///     Some(&TimeoutError { seconds: 30, retries: 3 })
+	/// );
+	///
+	/// assert!(x.downcast_mut::<core::fmt::Error>().is_none());
+	/// assert!(RawUnexpected::msg("failed").downcast_mut::<TimeoutError>().is_none());
+	/// ```
+	#[must_use]
+	#[cfg(any(feature = "std", all(feature = "alloc", feature = "core-error")))]
+	pub fn downcast_mut<E: Error + 'static>(&mut self) -> Option<&mut E> {
+		match &mut self.internal {
+			ErrorTy::None | ErrorTy::Message(_) => None,
+			ErrorTy::Error(e) => e.downcast_mut(),
+			ErrorTy::Context(_, inner) => inner.0.downcast_mut(),
+		}
+	}
+
+	/// Convert the `RawUnexpected` into the original error, if it has type
+	/// `E`.
+	///
+	/// If the error has a different type, or if `self` was created using
+	/// [`RawUnexpected::msg`] or [`RawUnexpected::none`], then `self` is
+	/// returned unchanged. Any metadata attached to `self` is discarded if the
+	/// downcast succeeds.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::error::Error;
+	/// use std::fmt::{self, Display};
+	///
+	/// use exun::*;
+	///
+	/// #[derive(Debug, PartialEq)]
+	/// struct TimeoutError(u32);
+	///
+	/// impl Display for TimeoutError {
+	///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+	///         write!(f, "timed out after {} seconds", self.0)
+	///     }
+	/// }
+	///
+	/// impl Error for TimeoutError {}
+	///
+	/// let x = RawUnexpected::new(TimeoutError(30));
+	/// assert_eq!(x.downcast::<TimeoutError>().unwrap(), TimeoutError(30));
+	///
+	/// let x = RawUnexpected::new(TimeoutError(30));
+	/// let x = x.downcast::<core::fmt::Error>().unwrap_err();
+	/// assert_eq!(x.to_string(), "timed out after 30 seconds");
+	///
+	/// let x = RawUnexpected::msg("failed");
+	/// assert!(x.downcast::<TimeoutError>().is_err());
+	/// ```
+	#[cfg(any(feature = "std", all(feature = "alloc", feature = "core-error")))]
+	pub fn downcast<E: Error + Send + Sync + 'static>(self) -> Result<E, Self> {
+		let internal = match self.internal {
+			ErrorTy::Error(e) => match e.downcast::<E>() {
+				Ok(e) => return Ok(*e),
+				Err(e) => ErrorTy::Error(e),
+			},
+			ErrorTy::Context(c, inner) => match inner.0.downcast::<E>() {
+				Ok(e) => return Ok(e),
+				Err(inner) => ErrorTy::Context(c, Box::new(UnexpectedError(inner))),
+			},
+			internal => internal,
+		};
+
+		Err(Self { internal, ..self })
+	}
+
+	/// Attempt to take ownership of the original error as a concrete type.
+	///
+	/// This is an alias for [`RawUnexpected::downcast`]. If the original
+	/// error isn't of type `E`, then `self` is returned unchanged.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::num::ParseIntError;
+	///
+	/// use exun::*;
+	///
+	/// let x = RawUnexpected::new(core::fmt::Error);
+	/// assert_eq!(x.try_into_inner::<core::fmt::Error>().unwrap(), core::fmt::Error);
+	///
+	/// let x = RawUnexpected::new(core::fmt::Error).context("formatting");
+	/// let x = x.try_into_inner::<ParseIntError>().unwrap_err();
+	/// assert_eq!(x.to_string(), "formatting");
+	/// assert!(x.is::<core::fmt::Error>());
+	/// ```
+	#[cfg(any(feature = "std", all(feature = "alloc", feature = "core-error")))]
+	pub fn try_into_inner<E: Error + Send + Sync + 'static>(self) -> Result<E, Self> {
+		self.downcast()
+	}
 
-#[cfg(feature = "std")]
-use std::error::Error;
+	/// Wrap the error with a message describing what was happening when it
+	/// occurred.
+	///
+	/// The context becomes the message that's displayed, while the original
+	/// error is still reachable through [`RawUnexpected::source`]. Any
+	/// metadata attached to `self` is kept. Contexts don't affect
+	/// [`RawUnexpected::downcast_ref`] and similar methods, which still look
+	/// at the original error.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::num::ParseIntError;
+	///
+	/// use exun::*;
+	///
+	/// # #[cfg(feature = "std")]
+	/// # fn main() {
+	/// let parse_error = "a".parse::<u32>().unwrap_err();
+	/// let x = RawUnexpected::new(parse_error.clone()).context("invalid port");
+	/// assert_eq!(x.to_string(), "invalid port");
+	/// assert_eq!(x.source().unwrap().to_string(), parse_error.to_string());
+	/// assert_eq!(x.downcast_ref::<ParseIntError>(), Some(&parse_error));
+	///
+	/// let x = x.context("failed to load config");
+	/// assert_eq!(x.to_string(), "failed to load config");
+	/// let chain: Vec<String> = std::iter::successors(x.source(), |e| e.source())
+	///     .map(|e| e.to_string())
+	///     .collect();
+	/// assert_eq!(chain, ["invalid port", &parse_error.to_string()]);
+	///
+	/// let x = RawUnexpected::msg("disk full").context("failed to save");
+	/// assert_eq!(x.to_string(), "failed to save");
+	/// assert_eq!(x.source().unwrap().to_string(), "disk full");
+	/// assert!(x.source().unwrap().source().is_none());
+	/// # }
+	/// # #[cfg(not(feature = "std"))]
+	/// # fn main() {}
+	/// ```
+	#[cfg(feature = "alloc")]
+	#[must_use]
+	pub fn context<C: Display + Send + Sync + 'static>(self, ctx: C) -> Self {
+		let inner = Self {
+			internal: self.internal,
+			..Self::none()
+		};
 
-pub trait Errorable: Display + Debug + Send + Sync {}
-impl<T: Display + Debug + Send + Sync + ?Sized> Errorable for T {}
+		Self {
+			internal: ErrorTy::Context(ctx.to_string(), Box::new(UnexpectedError(inner))),
+			..self
+		}
+	}
 
-#[derive(Debug)]
-enum ErrorTy {
-	None,
+	/// Create a copy of the error, containing only its message.
+	///
+	/// `RawUnexpected` can't implement [`Clone`], because the original error
+	/// might not be clonable. This is a lossy alternative: the copy is
+	/// created using [`RawUnexpected::msg`] with the [`Display`] output of
+	/// `self`, so the source chain and the type of the original error are
+	/// lost. Any metadata is copied.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x = RawUnexpected::msg("failed").with_metadata("attempt", 3);
+	/// let y = x.clone_as_msg();
+	/// assert_eq!(y.to_string(), x.to_string());
+	/// assert_eq!(y.metadata(), x.metadata());
+	///
+	/// # #[cfg(feature = "std")]
+	/// # {
+	/// let x = RawUnexpected::new(core::fmt::Error).context("failed to write");
+	/// let y = x.clone_as_msg();
+	/// assert_eq!(y.to_string(), "failed to write");
+	/// assert!(y.is_msg());
+	/// assert!(y.source().is_none());
+	/// # }
+	/// ```
 	#[cfg(feature = "alloc")]
-	Message(Box<dyn Errorable + 'static>),
+	#[must_use]
+	pub fn clone_as_msg(&self) -> Self {
+		Self {
+			metadata: self.metadata.clone(),
+			..Self::msg(self.to_string())
+		}
+	}
+
+	/// Attach a key-value pair to the error.
+	///
+	/// This can be used to tag the error with information, such as a request
+	/// ID, which isn't part of the error message itself. No memory is
+	/// allocated until the first pair is attached.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x = RawUnexpected::msg("failed")
+	///     .with_metadata("request_id", 42)
+	///     .with_metadata("operation", "upload");
+	///
+	/// assert_eq!(
+	///     x.metadata(),
+	///     &[
+	///         ("request_id", "42".to_string()),
+	///         ("operation", "upload".to_string()),
+	///     ]
+	/// );
+	/// ```
+	#[cfg(feature = "alloc")]
+	#[must_use]
+	pub fn with_metadata(mut self, key: &'static str, value: impl Display) -> Self {
+		self.metadata.push((key, value.to_string()));
+		self
+	}
+
+	/// Get the key-value pairs attached with [`RawUnexpected::with_metadata`].
+	///
+	/// The pairs are returned in the order they were attached.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x = RawUnexpected::none();
+	/// assert!(x.metadata().is_empty());
+	///
+	/// let x = x.with_metadata("attempt", 3);
+	/// assert_eq!(x.metadata(), &[("attempt", "3".to_string())]);
+	/// ```
+	#[cfg(feature = "alloc")]
+	#[must_use]
+	pub fn metadata(&self) -> &[(&'static str, String)] {
+		&self.metadata
+	}
+
+	/// Get the backtrace captured when `self` was created.
+	///
+	/// Like [`Backtrace::capture`], a backtrace is only captured if the
+	/// `RUST_BACKTRACE` or `RUST_LIB_BACKTRACE` environment variables are
+	/// set. Otherwise, or if `self` was created using
+	/// [`RawUnexpected::none`], this will return [`None`]. Wrapping the error
+	/// with a [context](RawUnexpected::context) keeps the original backtrace.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x = RawUnexpected::msg("failed");
+	/// if let Some(backtrace) = x.backtrace() {
+	///     println!("{}", backtrace);
+	/// }
+	///
+	/// assert!(RawUnexpected::none().backtrace().is_none());
+	/// ```
+	#[cfg(feature = "backtrace")]
+	#[must_use]
+	#[allow(clippy::incompatible_msrv)]
+	pub const fn backtrace(&self) -> Option<&Backtrace> {
+		self.backtrace.as_ref()
+	}
+
+	/// Convert the `RawUnexpected` into an [`io::Error`].
+	///
+	/// If `self` holds an [`io::Error`] without any contexts, then it is
+	/// returned directly. Otherwise, the `RawUnexpected` is wrapped in an
+	/// [`io::Error`] with the [`Other`] kind. This includes an [`io::Error`]
+	/// with a context, so that the context isn't lost. This is useful for returning an
+	/// unexpected error from a function that must return an [`io::Result`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::io;
+	///
+	/// use exun::*;
+	///
+	/// let x = RawUnexpected::new(io::Error::from(io::ErrorKind::NotFound));
+	/// assert_eq!(x.into_io_error().kind(), io::ErrorKind::NotFound);
+	///
+	/// let x = RawUnexpected::msg("failed");
+	/// let error = x.into_io_error();
+	/// assert_eq!(error.kind(), io::ErrorKind::Other);
+	/// assert_eq!(error.to_string(), "failed");
+	///
+	/// let x = RawUnexpected::new(io::Error::from(io::ErrorKind::NotFound)).context("failed to open");
+	/// let error = x.into_io_error();
+	/// assert_eq!(error.kind(), io::ErrorKind::Other);
+	/// assert_eq!(error.to_string(), "failed to open");
+	/// ```
+	///
+	/// [`io::Error`]: std::io::Error
+	/// [`io::Result`]: std::io::Result
+	/// [`Other`]: std::io::ErrorKind::Other
 	#[cfg(feature = "std")]
-	Error(Box<dyn Error + Send + Sync + 'static>),
+	#[must_use]
+	pub fn into_io_error(self) -> std::io::Error {
+		use std::io;
+
+		let internal = match self.internal {
+			ErrorTy::Error(e) => match e.downcast::<io::Error>() {
+				Ok(io_error) => return *io_error,
+				Err(e) => ErrorTy::Error(e),
+			},
+			internal => internal,
+		};
+
+		io::Error::new(
+			io::ErrorKind::Other,
+			UnexpectedError(Self { internal, ..self }),
+		)
+	}
 }
 
-/// A wrapper for an error that isn't expected to occur.
+#[cfg(feature = "single-thread")]
+#[derive(Debug)]
+enum LocalErrorTy {
+	None,
+	Message(Box<dyn LocalErrorable + 'static>),
+	#[cfg(any(feature = "std", feature = "core-error"))]
+	Error(Box<dyn Error + 'static>),
+}
+
+/// A wrapper for an error that isn't expected to occur, which doesn't need
+/// to be thread-safe.
 ///
-/// This implements [`From<T>`] where `T` implements [`Error`], [`Send`],
-/// [`Sync`] and `'static` for easy conversion. Because of this, it cannot
-/// itself implement [`Error`]. If you need a type that implements [`Error`]
-/// but doesn't implement `From<Error>`, use [`UnexpectedError`].
+/// This works like [`RawUnexpected`], except that the errors it holds don't
+/// need to implement [`Send`] or [`Sync`]. This makes it possible to bottle
+/// up errors which contain, for example, an `Rc`. Because of this,
+/// `RawUnexpectedLocal` itself is neither [`Send`] nor [`Sync`].
+#[cfg(feature = "single-thread")]
 #[derive(Debug)]
-pub struct RawUnexpected {
-	internal: ErrorTy,
+pub struct RawUnexpectedLocal {
+	internal: LocalErrorTy,
 }
 
-impl Display for RawUnexpected {
+#[cfg(feature = "single-thread")]
+impl Display for RawUnexpectedLocal {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		match &self.internal {
-			ErrorTy::None => Display::fmt("Called `unexpect` on a `None` value", f),
-			#[cfg(feature = "alloc")]
-			ErrorTy::Message(m) => Display::fmt(&m, f),
-			#[cfg(feature = "std")]
-			ErrorTy::Error(e) => Display::fmt(&e, f),
+			LocalErrorTy::None => Display::fmt("Called `unexpect` on a `None` value", f),
+			LocalErrorTy::Message(m) => Display::fmt(&m, f),
+			#[cfg(any(feature = "std", feature = "core-error"))]
+			LocalErrorTy::Error(e) => Display::fmt(&e, f),
 		}
 	}
 }
 
-#[cfg(feature = "std")]
-impl<T: Error + Send + Sync + 'static> From<T> for RawUnexpected {
+#[cfg(all(
+	feature = "single-thread",
+	any(feature = "std", feature = "core-error")
+))]
+impl<T: Error + 'static> From<T> for RawUnexpectedLocal {
 	fn from(e: T) -> Self {
 		Self::new(e)
 	}
 }
 
-impl RawUnexpected {
-	/// Create a new `RawUnexpected` from any [`Error`] type.
+#[cfg(feature = "single-thread")]
+impl RawUnexpectedLocal {
+	/// Create a new `RawUnexpectedLocal` from any [`Error`] type.
 	///
-	/// The error must be thread-safe and `'static` so that the
-	/// `RawUnexpected` will be too.
+	/// Unlike [`RawUnexpected::new`], the error doesn't need to be
+	/// thread-safe.
 	///
 	/// # Examples
 	///
 	/// ```
+	/// use std::error::Error;
+	/// use std::fmt::{self, Display};
+	/// use std::rc::Rc;
+	///
 	/// use exun::*;
 	///
-	/// let x = RawUnexpected::new(core::fmt::Error);
+	/// #[derive(Debug)]
+	/// struct SharedError(Rc<str>);
+	///
+	/// impl Display for SharedError {
+	///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+	///         write!(f, "{}", self.0)
+	///     }
+	/// }
+	///
+	/// impl Error for SharedError {}
+	///
+	/// let x = RawUnexpectedLocal::new(SharedError(Rc::from("failed")));
+	/// assert_eq!(x.to_string(), "failed");
+	/// assert!(x.source().is_some());
 	/// ```
-	#[cfg(feature = "std")]
+	#[cfg(any(feature = "std", feature = "core-error"))]
 	#[must_use]
-	pub fn new<E: Error + Send + Sync + 'static>(error: E) -> Self {
+	pub fn new<E: Error + 'static>(error: E) -> Self {
 		Self {
-			internal: ErrorTy::Error(Box::new(error)),
+			internal: LocalErrorTy::Error(Box::new(error)),
 		}
 	}
 
-	/// Create a new `RawUnexpected` from a printable error message.
+	/// Create a new `RawUnexpectedLocal` from a printable error message.
 	///
-	/// If the argument implements [`Error`], prefer [`RawUnexpected::new`]
-	/// instead, which preserves the source.
+	/// If the argument implements [`Error`], prefer
+	/// [`RawUnexpectedLocal::new`] instead, which preserves the source.
 	///
 	/// # Examples
 	///
 	/// ```
+	/// use std::rc::Rc;
+	///
 	/// use exun::*;
 	///
-	/// let x = RawUnexpected::msg("failed");
+	/// let x = RawUnexpectedLocal::msg(Rc::<str>::from("failed"));
+	/// assert_eq!(x.to_string(), "failed");
 	/// ```
-	#[cfg(feature = "alloc")]
 	#[must_use]
-	pub fn msg<E: Display + Debug + Send + Sync + 'static>(error: E) -> Self {
+	pub fn msg<E: Display + Debug + 'static>(error: E) -> Self {
 		Self {
-			internal: ErrorTy::Message(Box::new(error)),
+			internal: LocalErrorTy::Message(Box::new(error)),
 		}
 	}
 
-	/// Create a new `RawUnexpected` that is simply empty.
-	///
-	/// This is used for converting an [`Option<T>`] to a
-	/// [`Result<T, RawUnexpected>`].
+	/// Create a new `RawUnexpectedLocal` that is simply empty.
 	///
 	/// # Examples
 	///
 	/// ```
 	/// use exun::*;
 	///
-	/// let x = RawUnexpected::none();
+	/// let x = RawUnexpectedLocal::none();
 	/// ```
 	#[must_use]
-	pub fn none() -> Self {
+	pub const fn none() -> Self {
 		Self {
-			internal: ErrorTy::None,
+			internal: LocalErrorTy::None,
 		}
 	}
 
 	/// Get the original error.
 	///
 	/// This will return [`None`] if `self` was created using
-	/// [`RawUnexpected::msg`].
+	/// [`RawUnexpectedLocal::msg`].
 	///
 	/// # Examples
 	///
 	/// ```
 	/// use exun::*;
 	///
-	/// let x = RawUnexpected::new(core::fmt::Error);
+	/// let x = RawUnexpectedLocal::new(core::fmt::Error);
 	/// assert!(x.source().is_some());
 	///
-	/// let x = RawUnexpected::msg("failed");
+	/// let x = RawUnexpectedLocal::msg("failed");
 	/// assert!(x.source().is_none());
 	/// ```
 	#[must_use]
-	#[cfg(feature = "std")]
+	#[cfg(any(feature = "std", feature = "core-error"))]
 	pub fn source(&self) -> Option<&(dyn Error + 'static)> {
 		match &self.internal {
-			ErrorTy::None => None,
-			#[cfg(feature = "alloc")]
-			ErrorTy::Message(_) => None,
-			#[cfg(feature = "std")]
-			ErrorTy::Error(e) => Some(&**e),
+			LocalErrorTy::None | LocalErrorTy::Message(_) => None,
+			LocalErrorTy::Error(e) => Some(&**e),
+		}
+	}
+}
+
+/// Displays a [`RawUnexpected`], followed by each error in its source chain.
+pub struct DisplayChain<'a>(pub &'a RawUnexpected);
+
+impl Display for DisplayChain<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		Display::fmt(self.0, f)?;
+
+		#[cfg(any(feature = "std", feature = "core-error"))]
+		{
+			let mut source = self.0.cause();
+			while let Some(error) = source {
+				write!(f, ": {}", error)?;
+				source = error.source();
+			}
 		}
+
+		Ok(())
+	}
+}
+
+/// An iterator over the chain of errors in a [`RawUnexpected`].
+///
+/// This is created by [`RawUnexpected::chain`].
+#[cfg(any(feature = "std", feature = "core-error"))]
+#[derive(Debug, Clone)]
+pub struct Chain<'a> {
+	next: Option<&'a (dyn Error + 'static)>,
+}
+
+#[cfg(any(feature = "std", feature = "core-error"))]
+impl<'a> Iterator for Chain<'a> {
+	type Item = &'a (dyn Error + 'static);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let error = self.next?;
+		self.next = error.source();
+		Some(error)
 	}
 }
 
+#[cfg(any(feature = "std", feature = "core-error"))]
+impl FusedIterator for Chain<'_> {}
+
 /// An error that isn't expected to occur.
 ///
 /// This implements [`Error`]. Because of this, it cannot implement
 /// `From<Error>`. If that's something you need, try [`RawUnexpected`].
+///
+/// Instead, `From` is implemented for a few of the most common error types
+/// in the standard library, so that they can be used with the try operator:
+/// [`io::Error`], [`ParseIntError`], [`Utf8Error`] and [`FromUtf8Error`].
+///
+/// Like any other error, it can be returned from `main`, in which case the
+/// program exits with a failure code. This also works for an [`Exun`]
+/// containing an `UnexpectedError`.
+///
+/// # Examples
+///
+/// ```
+/// use exun::*;
+///
+/// # #[cfg(feature = "std")]
+/// # fn main() {
+/// fn parse(bytes: Vec<u8>) -> Result<i32, UnexpectedError> {
+///     let text = String::from_utf8(bytes)?;
+///     Ok(text.parse()?)
+/// }
+///
+/// assert_eq!(parse(b"42".to_vec()).unwrap(), 42);
+/// assert_eq!(parse(b"forty-two".to_vec()).unwrap_err().to_string(), "invalid digit found in string");
+/// assert!(parse(vec![0xff]).is_err());
+///
+/// fn first_line(bytes: &[u8]) -> Result<&str, UnexpectedError> {
+///     Ok(core::str::from_utf8(bytes)?.lines().next().unwrap_or_default())
+/// }
+///
+/// assert_eq!(first_line(b"foo\nbar").unwrap(), "foo");
+/// assert!(first_line(&[0xff]).is_err());
+///
+/// fn read(path: &str) -> Result<String, UnexpectedError> {
+///     Ok(std::fs::read_to_string(path)?)
+/// }
+///
+/// assert!(read("this/file/does/not/exist").is_err());
+/// # }
+/// # #[cfg(not(feature = "std"))]
+/// # fn main() {}
+/// ```
+///
+/// [`Exun`]: crate::Exun
+/// [`io::Error`]: std::io::Error
+/// [`ParseIntError`]: core::num::ParseIntError
+/// [`Utf8Error`]: core::str::Utf8Error
 #[derive(Debug)]
 pub struct UnexpectedError(RawUnexpected);
 
@@ -159,7 +1256,7 @@ impl UnexpectedError {
 	///
 	/// let x = UnexpectedError::new(core::fmt::Error);
 	/// ```
-	#[cfg(feature = "std")]
+	#[cfg(any(feature = "std", all(feature = "alloc", feature = "core-error")))]
 	#[must_use]
 	pub fn new<E: Error + Send + Sync + 'static>(error: E) -> Self {
 		Self(RawUnexpected::new(error))
@@ -183,6 +1280,42 @@ impl UnexpectedError {
 		Self(RawUnexpected::msg(error))
 	}
 
+	/// Create a new `UnexpectedError` from any [`Error`] type.
+	///
+	/// This is an alias for [`UnexpectedError::new`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x = UnexpectedError::from_err(core::fmt::Error);
+	/// ```
+	#[cfg(any(feature = "std", all(feature = "alloc", feature = "core-error")))]
+	#[must_use]
+	#[inline]
+	pub fn from_err<E: Error + Send + Sync + 'static>(error: E) -> Self {
+		Self::new(error)
+	}
+
+	/// Create a new `UnexpectedError` from a printable error message.
+	///
+	/// This is an alias for [`UnexpectedError::msg`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use exun::*;
+	///
+	/// let x = UnexpectedError::from_msg("failed");
+	/// ```
+	#[cfg(feature = "alloc")]
+	#[must_use]
+	#[inline]
+	pub fn from_msg<E: Display + Debug + Send + Sync + 'static>(error: E) -> Self {
+		Self::msg(error)
+	}
+
 	/// Create a new `RawUnexpected` that is simply empty.
 	///
 	/// This is used for converting an [`Option<T>`] to a
@@ -196,9 +1329,128 @@ impl UnexpectedError {
 	/// let x = UnexpectedError::none();
 	/// ```
 	#[must_use]
-	pub fn none() -> Self {
+	pub const fn none() -> Self {
 		Self(RawUnexpected::none())
 	}
+
+	/// Returns `self` as a `&dyn Error`.
+	///
+	/// This allows an `UnexpectedError` to be passed to functions which take
+	/// a `&dyn Error`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::error::Error;
+	///
+	/// use exun::*;
+	///
+	/// fn describe(e: &dyn Error) -> String {
+	///     format!("error: {}", e)
+	/// }
+	///
+	/// # #[cfg(feature = "alloc")]
+	/// # fn main() {
+	/// let x = UnexpectedError::msg("failed");
+	/// assert_eq!(describe(x.as_error()), "error: failed");
+	/// # }
+	/// # #[cfg(not(feature = "alloc"))]
+	/// # fn main() {}
+	/// ```
+	#[must_use]
+	#[cfg(any(feature = "std", feature = "core-error"))]
+	pub fn as_error(&self) -> &(dyn Error + 'static) {
+		self
+	}
+
+	/// Write a report of the error to `w`.
+	///
+	/// The report contains the error message, followed by each error in its
+	/// source chain, and any metadata attached to the error, each on its own
+	/// line. With the `backtrace` feature, the backtrace is written at the
+	/// end, if one was captured. This is useful for crash handlers that need
+	/// to save the error to a file.
+	///
+	/// The report doesn't include the location where the error was created,
+	/// because `UnexpectedError` doesn't record it. The backtrace can be used
+	/// to find it instead.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::error::Error;
+	/// use std::fmt::{self, Display};
+	/// use std::num::ParseIntError;
+	///
+	/// use exun::*;
+	///
+	/// #[derive(Debug)]
+	/// struct ConfigError(ParseIntError);
+	///
+	/// impl Display for ConfigError {
+	///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+	///         write!(f, "invalid config")
+	///     }
+	/// }
+	///
+	/// impl Error for ConfigError {
+	///     fn source(&self) -> Option<&(dyn Error + 'static)> {
+	///         Some(&self.0)
+	///     }
+	/// }
+	///
+	/// let parse_error = "a".parse::<u32>().unwrap_err();
+	/// let x = RawUnexpected::new(ConfigError(parse_error)).with_metadata("line", 3);
+	/// let x = UnexpectedError::from(x);
+	///
+	/// let mut report = Vec::new();
+	/// x.dump(&mut report).unwrap();
+	/// let report = String::from_utf8(report).unwrap();
+	/// // with the `backtrace` feature, a backtrace may follow
+	/// assert_eq!(
+	///     report.lines().take(7).collect::<Vec<_>>(),
+	///     [
+	///         "invalid config",
+	///         "",
+	///         "Caused by:",
+	///         "    0: invalid digit found in string",
+	///         "",
+	///         "Metadata:",
+	///         "    line: 3",
+	///     ]
+	/// );
+	/// ```
+	#[cfg(feature = "std")]
+	pub fn dump(&self, w: &mut impl std::io::Write) -> std::io::Result<()> {
+		writeln!(w, "{}", self.0)?;
+
+		let mut source = self.0.cause();
+		if source.is_some() {
+			writeln!(w, "\nCaused by:")?;
+		}
+
+		let mut index = 0;
+		while let Some(error) = source {
+			writeln!(w, "    {}: {}", index, error)?;
+			source = error.source();
+			index += 1;
+		}
+
+		if !self.0.metadata().is_empty() {
+			writeln!(w, "\nMetadata:")?;
+		}
+
+		for (key, value) in self.0.metadata() {
+			writeln!(w, "    {}: {}", key, value)?;
+		}
+
+		#[cfg(feature = "backtrace")]
+		if let Some(backtrace) = self.0.backtrace() {
+			writeln!(w, "\nBacktrace:\n{}", backtrace)?;
+		}
+
+		Ok(())
+	}
 }
 
 impl From<RawUnexpected> for UnexpectedError {
@@ -221,13 +1473,41 @@ impl From<String> for UnexpectedError {
 	}
 }
 
+#[cfg(feature = "std")]
+impl From<std::io::Error> for UnexpectedError {
+	fn from(value: std::io::Error) -> Self {
+		Self::new(value)
+	}
+}
+
+#[cfg(any(feature = "std", all(feature = "alloc", feature = "core-error")))]
+impl From<core::num::ParseIntError> for UnexpectedError {
+	fn from(value: core::num::ParseIntError) -> Self {
+		Self::new(value)
+	}
+}
+
+#[cfg(any(feature = "std", all(feature = "alloc", feature = "core-error")))]
+impl From<core::str::Utf8Error> for UnexpectedError {
+	fn from(value: core::str::Utf8Error) -> Self {
+		Self::new(value)
+	}
+}
+
+#[cfg(any(feature = "std", all(feature = "alloc", feature = "core-error")))]
+impl From<FromUtf8Error> for UnexpectedError {
+	fn from(value: FromUtf8Error) -> Self {
+		Self::new(value)
+	}
+}
+
 impl Display for UnexpectedError {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		Display::fmt(&self.0, f)
 	}
 }
 
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", feature = "core-error"))]
 impl Error for UnexpectedError {
 	fn source(&self) -> Option<&(dyn Error + 'static)> {
 		self.0.source()
@@ -0,0 +1,22 @@
+#![cfg(all(feature = "alloc", not(feature = "std")))]
+
+//! The library is built with `alloc` but without `std` here, so only the
+//! message-based conversions are available.
+
+use exun::{RawUnexpected, ResultMsgExt};
+
+#[test]
+fn option_unexpect_msg() {
+	let none: Option<i32> = None;
+	assert!(none.unexpect_msg().unwrap_err().is_none());
+
+	let some: Result<i32, RawUnexpected> = Some(3).unexpect_msg();
+	assert_eq!(some.unwrap(), 3);
+}
+
+#[test]
+fn result_unexpect_msg() {
+	let res: Result<i32, &str> = Err("failure");
+	let err = res.unexpect_msg().unwrap_err();
+	assert!(err.is_msg());
+}
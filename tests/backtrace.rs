@@ -0,0 +1,31 @@
+#![cfg(feature = "backtrace")]
+
+use std::env;
+
+use exun::{RawUnexpected, UnexpectedError};
+
+// whether backtraces are enabled is only checked once, so everything is
+// checked in a single test
+#[test]
+fn backtrace_is_captured() {
+	env::set_var("RUST_LIB_BACKTRACE", "1");
+
+	let x = RawUnexpected::msg("failed");
+	assert!(x.backtrace().is_some());
+
+	let x = RawUnexpected::new(core::fmt::Error).context("failed to write");
+	assert!(x.backtrace().is_some());
+
+	assert!(RawUnexpected::none().backtrace().is_none());
+
+	let x = UnexpectedError::msg("failed");
+	let mut report = Vec::new();
+	x.dump(&mut report).unwrap();
+	let report = String::from_utf8(report).unwrap();
+	assert!(report.starts_with("failed\n\nBacktrace:\n"));
+
+	let x = UnexpectedError::none();
+	let mut report = Vec::new();
+	x.dump(&mut report).unwrap();
+	assert!(!String::from_utf8(report).unwrap().contains("Backtrace:"));
+}
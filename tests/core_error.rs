@@ -0,0 +1,32 @@
+#![cfg(all(feature = "core-error", not(feature = "std")))]
+
+//! The library is built without `std` here, so these impls come from
+//! `core::error::Error`.
+
+use core::error::Error;
+
+use exun::{Expected, Exun, UnexpectedError};
+
+fn assert_error<T: Error>() {}
+
+#[test]
+fn implements_core_error() {
+	assert_error::<UnexpectedError>();
+	assert_error::<Exun<core::fmt::Error, UnexpectedError>>();
+}
+
+#[test]
+fn exun_source() {
+	let x: Exun<core::fmt::Error, UnexpectedError> = Expected(core::fmt::Error);
+	assert!(x.source().unwrap().is::<core::fmt::Error>());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn unexpected_error_source() {
+	let x = UnexpectedError::new(core::fmt::Error);
+	assert!(x.source().unwrap().is::<core::fmt::Error>());
+
+	let x = UnexpectedError::msg("failed");
+	assert!(x.source().is_none());
+}
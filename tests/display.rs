@@ -0,0 +1,64 @@
+//! The `Display` impls should forward the formatter, including fill,
+//! alignment, width and precision, straight through to the inner value.
+
+use exun::{Expected, Exun, RawUnexpected, Unexpected, UnexpectedError};
+
+#[test]
+fn exun_pads() {
+	let x: Exun<&str, &str> = Expected("expected");
+	assert_eq!(format!("{:>12}", x), "    expected");
+
+	let x: Exun<&str, &str> = Unexpected("unexpected");
+	assert_eq!(format!("{:-<12}", x), "unexpected--");
+	assert_eq!(format!("{:.3}", x), "une");
+}
+
+#[test]
+fn none_pads() {
+	let x = RawUnexpected::none();
+	assert_eq!(
+		format!("{:>40}", x),
+		format!("{:>40}", "Called `unexpect` on a `None` value")
+	);
+	assert_eq!(format!("{:.6}", x), "Called");
+
+	let x = UnexpectedError::none();
+	assert_eq!(format!("{:^40}", x), format!("{:^40}", x.to_string()));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn msg_pads() {
+	let x = RawUnexpected::msg("failure");
+	assert_eq!(format!("{:>20}", x), "             failure");
+
+	let x = UnexpectedError::msg("failure");
+	assert_eq!(format!("{:>20}", x), "             failure");
+	assert_eq!(format!("{:*^11.4}", x), "***fail****");
+}
+
+#[cfg(any(feature = "std", all(feature = "alloc", feature = "core-error")))]
+#[test]
+fn error_pads() {
+	let message = core::fmt::Error.to_string();
+
+	let x = RawUnexpected::new(core::fmt::Error);
+	assert_eq!(format!("{:>60}", x), format!("{:>60}", message));
+
+	let x = UnexpectedError::new(core::fmt::Error);
+	assert_eq!(format!("{:<60}|", x), format!("{:<60}|", message));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn context_pads() {
+	let x = RawUnexpected::msg("failure").context("loading");
+	assert_eq!(format!("{:>20}", x), "             loading");
+}
+
+#[cfg(feature = "single-thread")]
+#[test]
+fn local_pads() {
+	let x = exun::RawUnexpectedLocal::msg("failure");
+	assert_eq!(format!("{:>20}", x), "             failure");
+}
@@ -0,0 +1,41 @@
+#![cfg(feature = "std")]
+
+use std::env;
+use std::process::Command;
+
+use exun::{Expected, Exun, Unexpected};
+
+const CHILD_VAR: &str = "EXUN_EXPECTED_OR_EXIT_CHILD";
+
+fn run_child(test: &str) -> std::process::Output {
+	Command::new(env::current_exe().unwrap())
+		.args([test, "--exact", "--nocapture"])
+		.env(CHILD_VAR, "1")
+		.output()
+		.unwrap()
+}
+
+#[test]
+fn expected_continues() {
+	if env::var_os(CHILD_VAR).is_some() {
+		let x: Exun<u32, &str> = Expected(2);
+		assert_eq!(x.expected_or_exit(3), 2);
+		return;
+	}
+
+	let output = run_child("expected_continues");
+	assert!(output.status.success());
+}
+
+#[test]
+fn unexpected_exits() {
+	if env::var_os(CHILD_VAR).is_some() {
+		let x: Exun<u32, &str> = Unexpected("something went wrong");
+		x.expected_or_exit(3);
+		unreachable!("expected_or_exit should have exited");
+	}
+
+	let output = run_child("unexpected_exits");
+	assert_eq!(output.status.code(), Some(3));
+	assert!(String::from_utf8_lossy(&output.stderr).contains("something went wrong"));
+}
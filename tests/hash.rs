@@ -0,0 +1,32 @@
+//! The derived `Hash` impl writes the variant before the contained value, so
+//! `Expected(x)` and `Unexpected(x)` hash differently.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+
+use exun::{Expected, Exun, Unexpected};
+
+fn hash(x: &Exun<i32, i32>) -> u64 {
+	let mut hasher = DefaultHasher::new();
+	x.hash(&mut hasher);
+	hasher.finish()
+}
+
+#[test]
+fn variants_hash_differently() {
+	assert_ne!(hash(&Expected(1)), hash(&Unexpected(1)));
+	assert_eq!(hash(&Expected(1)), hash(&Expected(1)));
+}
+
+#[test]
+fn hash_set_keeps_both_variants() {
+	let mut set: HashSet<Exun<i32, i32>> = HashSet::new();
+	assert!(set.insert(Expected(1)));
+	assert!(set.insert(Unexpected(1)));
+	assert!(!set.insert(Expected(1)));
+
+	assert_eq!(set.len(), 2);
+	assert!(set.contains(&Expected(1)));
+	assert!(set.contains(&Unexpected(1)));
+}
@@ -0,0 +1,25 @@
+#![cfg(feature = "proptest")]
+
+use exun::Exun;
+use proptest::prelude::*;
+use proptest::strategy::ValueTree;
+use proptest::test_runner::TestRunner;
+
+proptest! {
+	#[test]
+	fn swap_round_trip(x in any::<Exun<u8, i32>>()) {
+		prop_assert_eq!(x.swap().swap(), x);
+	}
+}
+
+#[test]
+fn generates_both_variants() {
+	let mut runner = TestRunner::deterministic();
+	let strategy = any::<Exun<u8, i32>>();
+	let values: Vec<_> = (0..100)
+		.map(|_| strategy.new_tree(&mut runner).unwrap().current())
+		.collect();
+
+	assert!(values.iter().any(Exun::is_expected));
+	assert!(values.iter().any(Exun::is_unexpected));
+}
@@ -0,0 +1,23 @@
+#![cfg(feature = "quickcheck")]
+
+use exun::Exun;
+use quickcheck::{quickcheck, Arbitrary, Gen};
+
+quickcheck! {
+	fn map_identity(x: Exun<u8, i32>) -> bool {
+		x.map(|e| e) == x
+	}
+
+	fn shrinks_keep_the_variant(x: Exun<u8, i32>) -> bool {
+		x.shrink().all(|y| y.is_expected() == x.is_expected())
+	}
+}
+
+#[test]
+fn generates_both_variants() {
+	let mut g = Gen::new(100);
+	let values: Vec<Exun<u8, i32>> = (0..100).map(|_| Exun::arbitrary(&mut g)).collect();
+
+	assert!(values.iter().any(Exun::is_expected));
+	assert!(values.iter().any(Exun::is_unexpected));
+}
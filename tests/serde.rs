@@ -0,0 +1,19 @@
+#![cfg(feature = "serde")]
+
+use exun::{Expected, Exun, Unexpected};
+
+#[test]
+fn expected_round_trip() {
+	let x: Exun<u32, String> = Expected(404);
+	let json = serde_json::to_string(&x).unwrap();
+	assert_eq!(json, r#"{"Expected":404}"#);
+	assert_eq!(serde_json::from_str::<Exun<u32, String>>(&json).unwrap(), x);
+}
+
+#[test]
+fn unexpected_round_trip() {
+	let x: Exun<u32, String> = Unexpected("error".to_string());
+	let json = serde_json::to_string(&x).unwrap();
+	assert_eq!(json, r#"{"Unexpected":"error"}"#);
+	assert_eq!(serde_json::from_str::<Exun<u32, String>>(&json).unwrap(), x);
+}
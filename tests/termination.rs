@@ -0,0 +1,53 @@
+//! `main` can return any `Result<(), E>` where `E: Debug`, so this checks that
+//! returning an `UnexpectedError` or an `Exun` from `main` exits with a failure
+//! code. This test doesn't use the default test harness, so that it has its
+//! own `main`.
+
+#[cfg(feature = "std")]
+use std::env;
+#[cfg(feature = "std")]
+use std::process::Command;
+
+#[cfg(feature = "std")]
+use exun::{Expected, Exun, RawUnexpected, Unexpected, UnexpectedError};
+
+#[cfg(feature = "std")]
+const CHILD_VAR: &str = "EXUN_TERMINATION_CHILD";
+
+#[cfg(feature = "std")]
+fn child() -> Result<(), Exun<core::fmt::Error, UnexpectedError>> {
+	match env::var(CHILD_VAR).unwrap().as_str() {
+		"unexpected" => Err(Unexpected(UnexpectedError::msg("failed to start"))),
+		"raw" => Err(RawUnexpected::msg("failed to start"))?,
+		"expected" => Err(Expected(core::fmt::Error)),
+		_ => Ok(()),
+	}
+}
+
+#[cfg(feature = "std")]
+fn run_child(mode: &str) -> std::process::Output {
+	Command::new(env::current_exe().unwrap())
+		.env(CHILD_VAR, mode)
+		.output()
+		.unwrap()
+}
+
+#[cfg(feature = "std")]
+fn main() -> Result<(), Exun<core::fmt::Error, UnexpectedError>> {
+	if env::var_os(CHILD_VAR).is_some() {
+		return child();
+	}
+
+	for mode in &["unexpected", "raw", "expected"] {
+		let output = run_child(mode);
+		assert_eq!(output.status.code(), Some(1), "{}", mode);
+		assert!(String::from_utf8_lossy(&output.stderr).starts_with("Error: "));
+	}
+
+	assert!(run_child("ok").status.success());
+	println!("termination: ok");
+	Ok(())
+}
+
+#[cfg(not(feature = "std"))]
+fn main() {}
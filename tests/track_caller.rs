@@ -0,0 +1,69 @@
+#![cfg(feature = "std")]
+
+use std::cell::RefCell;
+use std::panic;
+
+use exun::{Expected, Exun, ResultExunExt, Unexpected};
+
+thread_local! {
+	static LOCATION: RefCell<Option<(String, u32)>> = RefCell::new(None);
+}
+
+fn panic_location(f: impl FnOnce() + panic::UnwindSafe) -> (String, u32) {
+	panic::set_hook(Box::new(|info| {
+		let location = info.location().unwrap();
+		let location = (location.file().to_string(), location.line());
+		LOCATION.with(|l| *l.borrow_mut() = Some(location));
+	}));
+	let result = panic::catch_unwind(f);
+	let _ = panic::take_hook();
+
+	assert!(result.is_err());
+	LOCATION.with(|l| l.borrow_mut().take().unwrap())
+}
+
+fn here(line: u32) -> (String, u32) {
+	(file!().to_string(), line)
+}
+
+// the panic hook is global, so everything is checked in a single test
+#[test]
+fn panics_report_the_caller() {
+	let unexpected: Exun<u32, &str> = Unexpected("error");
+	let expected: Exun<u32, &str> = Expected(2);
+
+	let location = panic_location(|| {
+		unexpected.unwrap();
+	});
+	assert_eq!(location, here(line!() - 2));
+
+	let location = panic_location(|| {
+		unexpected.expect("oh no");
+	});
+	assert_eq!(location, here(line!() - 2));
+
+	let location = panic_location(|| {
+		expected.unwrap_unexpected();
+	});
+	assert_eq!(location, here(line!() - 2));
+
+	let location = panic_location(|| {
+		expected.expect_unexpected("oh no");
+	});
+	assert_eq!(location, here(line!() - 2));
+
+	let location = panic_location(|| {
+		let _ = Err::<(), _>(unexpected).unwrap_result();
+	});
+	assert_eq!(location, here(line!() - 2));
+
+	let location = panic_location(|| {
+		Err::<(), _>(unexpected).unwrap_expected_err();
+	});
+	assert_eq!(location, here(line!() - 2));
+
+	let location = panic_location(|| {
+		Err::<(), _>(expected).unwrap_unexpected_err();
+	});
+	assert_eq!(location, here(line!() - 2));
+}